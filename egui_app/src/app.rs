@@ -9,14 +9,11 @@ const PADDING: f32 = 120.0;
 
 use eframe::epaint::{CircleShape, RectShape, TextShape};
 use egui::{Color32, Frame, Pos2};
-use lib_genetic_algo::Statistics;
+use lib_genetic_algo::{Statistics, StatisticsHistory};
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 use lib_simulation::*;
 use rand::thread_rng;
 
-struct Logger {
-    data: Vec<Statistics>,
-}
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
 pub struct TemplateApp {
@@ -30,7 +27,7 @@ pub struct TemplateApp {
     rect_scale: f32,
 
     #[serde(skip)]
-    logger: Logger,
+    history: StatisticsHistory,
     #[serde(skip)]
     generation: usize,
     // this how you opt-out of serialization of a member
@@ -42,13 +39,9 @@ impl Default for TemplateApp {
             // Example stuff:
             label: "Hello World!".to_owned(),
             rect_scale: 0.7,
-            simulation: Simulation::randomize(&mut thread_rng()),
-            statistics: Statistics {
-                avg_fitness: 0.0,
-                max_fitness: 0.0,
-                min_fitness: 0.0,
-            },
-            logger: Logger { data: Vec::new() },
+            simulation: Simulation::randomize(&mut thread_rng(), SimulationConfig::default()),
+            statistics: Statistics::default(),
+            history: StatisticsHistory::new(),
             generation: 1,
         }
     }
@@ -88,7 +81,7 @@ impl eframe::App for TemplateApp {
             rect_scale,
             simulation,
             statistics,
-            logger,
+            history,
             generation,
         } = self;
 
@@ -128,11 +121,11 @@ impl eframe::App for TemplateApp {
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
                         ui.label("Number of ants:");
-                        ui.label("50");
+                        ui.label(format!("{}", simulation.config().population_size));
                     });
                     ui.horizontal(|ui| {
                         ui.label("Food quantity:");
-                        ui.label("30");
+                        ui.label(format!("{}", simulation.config().food_count));
                     });
                     ui.horizontal(|ui| {
                         ui.label("Generation:");
@@ -162,12 +155,13 @@ impl eframe::App for TemplateApp {
                 });
             });
         egui::Window::new("Plot").open(&mut true).show(ctx, |ui| {
-            let avg_data = (0..logger.data.len())
-                .map(|point| Value::new(point as f32, logger.data[point].avg_fitness));
-            let max_data = (0..logger.data.len())
-                .map(|point| Value::new(point as f32, logger.data[point].max_fitness));
-            let min_data = (0..logger.data.len())
-                .map(|point| Value::new(point as f32, logger.data[point].min_fitness));
+            let data = history.generations();
+            let avg_data =
+                (0..data.len()).map(|point| Value::new(point as f32, data[point].avg_fitness));
+            let max_data =
+                (0..data.len()).map(|point| Value::new(point as f32, data[point].max_fitness));
+            let min_data =
+                (0..data.len()).map(|point| Value::new(point as f32, data[point].min_fitness));
 
             let plot_avg = Line::new(Values::from_values_iter(avg_data))
                 .fill(0.0)
@@ -199,9 +193,9 @@ impl eframe::App for TemplateApp {
             ui.heading("Simulation viewport");
             egui::warn_if_debug_build(ui);
             ui.small("The simulation is rendered at 60fps.");
-            if let Some(new_statistics) = simulation.step_forward(&mut thread_rng()) {
+            if let Some(new_statistics) = simulation.step_forward() {
                 *statistics = new_statistics.clone();
-                logger.data.push(new_statistics);
+                history.push(new_statistics);
                 *generation += 1;
             }
 
@@ -217,7 +211,12 @@ impl eframe::App for TemplateApp {
                 let (response, painter) =
                     ui.allocate_painter(ui.available_size_before_wrap(), egui::Sense::hover());
                 let galley = ui.painter().layout_no_wrap(
-                    format!("Ants: 50\nFood: 30\nGenerations: {}", *generation - 1),
+                    format!(
+                        "Ants: {}\nFood: {}\nGenerations: {}",
+                        simulation.config().population_size,
+                        simulation.config().food_count,
+                        *generation - 1
+                    ),
                     font_id.clone(),
                     Color32::LIGHT_GRAY,
                 );
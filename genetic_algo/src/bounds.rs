@@ -0,0 +1,196 @@
+use std::ops::RangeInclusive;
+
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::chromosome::Chromosome;
+use crate::gene::Gene;
+
+/// What [`Bounds::apply`] does with a gene outside its range.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoundPolicy {
+    /// Moves it to the nearest end of the range.
+    #[default]
+    Clamp,
+    /// Mirrors it back into the range at the end it crossed, as often as
+    /// needed.
+    Reflect,
+    /// Brings it in from the other end, as if the range were a circle.
+    Wrap,
+    /// Draws a fresh value uniformly from the range.
+    Resample,
+}
+
+/// Per-gene ranges, laid out in chromosome order, and the policy that
+/// brings genes back into them.
+///
+/// Ranges are real numbers whatever the gene type: integer genes are
+/// rounded after being brought back and binary genes, being 0 or 1, are
+/// left alone. Genes past the end of the layout are unbounded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bounds {
+    pub policy: BoundPolicy,
+    /// Runs of consecutive genes sharing a range; `None` for unbounded
+    /// ones.
+    layout: Vec<(usize, Option<RangeInclusive<f32>>)>,
+}
+
+impl Bounds {
+    /// An empty layout, which bounds nothing until segments are added.
+    pub fn new(policy: BoundPolicy) -> Self {
+        Self {
+            policy,
+            layout: Vec::new(),
+        }
+    }
+    /// Appends `count` genes bounded by `range`, which must be finite.
+    pub fn with(mut self, count: usize, range: RangeInclusive<f32>) -> Self {
+        assert!(range.start().is_finite() && range.end().is_finite());
+        assert!(range.start() <= range.end());
+        self.layout.push((count, Some(range)));
+        self
+    }
+    /// Appends `count` unbounded genes.
+    pub fn unbounded(mut self, count: usize) -> Self {
+        self.layout.push((count, None));
+        self
+    }
+    /// Range of the gene at `index`, if it has one.
+    pub fn range(&self, index: usize) -> Option<&RangeInclusive<f32>> {
+        let mut start = 0;
+
+        for (count, range) in &self.layout {
+            if index < start + count {
+                return range.as_ref();
+            }
+            start += count;
+        }
+        None
+    }
+    /// Brings every out-of-range gene of `chromosome` back into its range.
+    pub fn apply<G>(&self, rng: &mut dyn RngCore, chromosome: &mut Chromosome<G>)
+    where
+        G: Gene,
+    {
+        let ranges = self
+            .layout
+            .iter()
+            .flat_map(|(count, range)| std::iter::repeat_n(range, *count));
+
+        for (gene, range) in chromosome.iter_mut().zip(ranges) {
+            let Some(range) = range.as_ref().filter(|_| !gene.is_binary()) else {
+                continue;
+            };
+            let value = gene.to_f32();
+
+            if !range.contains(&value) {
+                *gene = gene.with_f32(self.constrain(rng, value, range));
+            }
+        }
+    }
+    fn constrain(&self, rng: &mut dyn RngCore, value: f32, range: &RangeInclusive<f32>) -> f32 {
+        let (low, high) = (*range.start(), *range.end());
+        let width = high - low;
+
+        if width == 0.0 || !value.is_finite() {
+            return match self.policy {
+                BoundPolicy::Resample => rng.gen_range(low..=high),
+                _ => value.clamp(low, high),
+            };
+        }
+
+        match self.policy {
+            BoundPolicy::Clamp => value.clamp(low, high),
+            BoundPolicy::Reflect => {
+                let offset = (value - low).rem_euclid(2.0 * width);
+                low + if offset > width {
+                    2.0 * width - offset
+                } else {
+                    offset
+                }
+            }
+            BoundPolicy::Wrap => low + (value - low).rem_euclid(width),
+            BoundPolicy::Resample => rng.gen_range(low..=high),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MixedGene;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn apply(policy: BoundPolicy, genes: &[f32]) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let bounds = Bounds::new(policy).with(genes.len() - 1, 0.0..=1.0);
+        let mut chromosome: Chromosome = genes.iter().copied().collect();
+
+        bounds.apply(&mut rng, &mut chromosome);
+        chromosome.into_iter().collect()
+    }
+
+    #[test]
+    fn policies_bring_genes_back_in_range() {
+        // The last gene is past the layout, so it's left alone.
+        let genes = [0.5, 1.25, -0.25, 2.75, 7.0];
+
+        assert_eq!(apply(BoundPolicy::Clamp, &genes), [0.5, 1.0, 0.0, 1.0, 7.0]);
+        assert_eq!(
+            apply(BoundPolicy::Reflect, &genes),
+            [0.5, 0.75, 0.25, 0.75, 7.0]
+        );
+        assert_eq!(
+            apply(BoundPolicy::Wrap, &genes),
+            [0.5, 0.25, 0.75, 0.75, 7.0]
+        );
+
+        let resampled = apply(BoundPolicy::Resample, &genes);
+        assert_eq!(resampled[0], 0.5);
+        assert!(resampled[..4].iter().all(|gene| (0.0..=1.0).contains(gene)));
+    }
+
+    #[test]
+    fn integer_genes_stay_integers() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let bounds = Bounds::new(BoundPolicy::Reflect)
+            .unbounded(1)
+            .with(2, 0.0..=10.0);
+        let mut chromosome: Chromosome<i32> = [-50, 13, -3].into_iter().collect();
+
+        bounds.apply(&mut rng, &mut chromosome);
+
+        assert_eq!(chromosome.into_iter().collect::<Vec<_>>(), [-50, 7, 3]);
+        assert_eq!(bounds.range(0), None);
+        assert_eq!(bounds.range(2), Some(&(0.0..=10.0)));
+    }
+
+    #[test]
+    fn binary_genes_are_left_alone() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        // Both switch values lie outside the range.
+        let bounds = Bounds::new(BoundPolicy::Resample).with(3, 0.25..=0.75);
+
+        let mut bits: Chromosome<bool> = [true, false, true].into_iter().collect();
+        bounds.apply(&mut rng, &mut bits);
+        assert_eq!(bits.into_iter().collect::<Vec<_>>(), [true, false, true]);
+
+        let mut mixed: Chromosome<MixedGene> = [
+            MixedGene::Binary(true),
+            MixedGene::Binary(false),
+            MixedGene::Real(2.0),
+        ]
+        .into_iter()
+        .collect();
+        bounds.apply(&mut rng, &mut mixed);
+        let mixed: Vec<_> = mixed.into_iter().collect();
+        assert_eq!(
+            mixed[..2],
+            [MixedGene::Binary(true), MixedGene::Binary(false)]
+        );
+        assert!(mixed[2]
+            .real()
+            .is_some_and(|gene| (0.25..=0.75).contains(&gene)));
+    }
+}
@@ -1,30 +1,46 @@
-pub struct Chromosome {
-    genome: Vec<f32>,
+use crate::gene::Gene;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chromosome<G = f32> {
+    genome: Vec<G>,
 }
 
-impl Chromosome {
+impl<G> Chromosome<G> {
     pub fn len(&self) -> usize {
         self.genome.len()
     }
-    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+    pub fn is_empty(&self) -> bool {
+        self.genome.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &G> {
         self.genome.iter()
     }
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut G> {
         self.genome.iter_mut()
     }
 }
 
-impl FromIterator<f32> for Chromosome {
-    fn from_iter<T: IntoIterator<Item = f32>>(iter: T) -> Self {
+impl<G> Chromosome<G>
+where
+    G: Gene,
+{
+    /// Every gene as a real number, see [`Gene::to_f32`].
+    pub fn to_reals(&self) -> Chromosome {
+        self.genome.iter().map(|gene| gene.to_f32()).collect()
+    }
+}
+
+impl<G> FromIterator<G> for Chromosome<G> {
+    fn from_iter<T: IntoIterator<Item = G>>(iter: T) -> Self {
         Self {
             genome: iter.into_iter().collect(),
         }
     }
 }
 
-impl IntoIterator for Chromosome {
-    type Item = f32;
-    type IntoIter = impl Iterator<Item = f32>;
+impl<G> IntoIterator for Chromosome<G> {
+    type Item = G;
+    type IntoIter = impl Iterator<Item = G>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.genome.into_iter()
@@ -1,14 +1,201 @@
 use crate::chromosome::Chromosome;
+use crate::MaybeSync;
 use rand::RngCore;
 
+mod arithmetic;
+mod blx;
+mod k_point;
+mod neuron;
+mod sbx;
+mod single_point;
 mod uniform_crossover;
 
+pub use arithmetic::ArithmeticCrossOver;
+pub use blx::BlxCrossOver;
+pub use k_point::KPointCrossOver;
+pub use neuron::NeuronCrossOver;
+pub use sbx::SbxCrossOver;
+pub use single_point::SinglePointCrossOver;
 pub use uniform_crossover::UniformCrossOver;
-pub trait Crossover {
+/// `G` is the type of the genes; see [`Gene`](crate::Gene) for which
+/// crossovers support which genes.
+pub trait Crossover<G = f32>: MaybeSync {
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
-        parent_one: &Chromosome,
-        parent_two: &Chromosome,
-    ) -> Chromosome;
+        parent_one: &Chromosome<G>,
+        parent_two: &Chromosome<G>,
+    ) -> Chromosome<G>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MixedGene;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const TRIALS: usize = 2000;
+
+    fn chromosome(genes: &[f32]) -> Chromosome {
+        genes.iter().copied().collect()
+    }
+
+    /// Crosses an all-zero parent with an all-one parent `TRIALS` times.
+    fn cross_binary(method: &impl Crossover, len: usize) -> Vec<Vec<f32>> {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let (zeros, ones) = (chromosome(&vec![0.0; len]), chromosome(&vec![1.0; len]));
+
+        (0..TRIALS)
+            .map(|_| {
+                method
+                    .crossover(&mut rng, &zeros, &ones)
+                    .into_iter()
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Crosses the single genes `a` and `b` `TRIALS` times.
+    fn cross_gene(method: &impl Crossover, a: f32, b: f32) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let (a, b) = (chromosome(&[a]), chromosome(&[b]));
+
+        (0..TRIALS)
+            .map(|_| {
+                method
+                    .crossover(&mut rng, &a, &b)
+                    .into_iter()
+                    .next()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn switches(genes: &[f32]) -> usize {
+        genes.windows(2).filter(|pair| pair[0] != pair[1]).count()
+    }
+
+    fn mean(values: &[f32]) -> f32 {
+        values.iter().sum::<f32>() / values.len() as f32
+    }
+
+    #[test]
+    fn single_point_cuts_uniformly() {
+        let children = cross_binary(&SinglePointCrossOver, 5);
+        let mut cuts = [0; 5];
+
+        for child in &children {
+            assert_eq!(child[0], 0.0);
+            assert_eq!(switches(child), 1);
+            cuts[child.iter().position(|&gene| gene == 1.0).unwrap()] += 1;
+        }
+
+        // Cut points 1..=4 should each get about a quarter of the trials.
+        assert_eq!(cuts[0], 0);
+        assert!(cuts[1..].iter().all(|&count| (400..600).contains(&count)));
+    }
+
+    #[test]
+    fn k_point_switches_parents_k_times() {
+        for k in [1, 2, 5] {
+            for child in cross_binary(&KPointCrossOver::new(k), 20) {
+                assert_eq!(child[0], 0.0);
+                assert_eq!(switches(&child), k);
+            }
+        }
+    }
+
+    #[test]
+    fn arithmetic_blends_with_alpha() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let child = ArithmeticCrossOver::new(0.25).crossover(
+            &mut rng,
+            &chromosome(&[0.0, 4.0]),
+            &chromosome(&[4.0, 0.0]),
+        );
+
+        assert_eq!(child, chromosome(&[3.0, 1.0]));
+    }
+
+    #[test]
+    fn blx_samples_the_widened_interval() {
+        let genes = cross_gene(&BlxCrossOver::new(0.5), 1.0, 3.0);
+
+        // [1, 3] widened by 0.5 * 2 on each side is [0, 4]; half of it lies
+        // outside the parents' interval.
+        assert!(genes.iter().all(|gene| (0.0..=4.0).contains(gene)));
+        assert!((mean(&genes) - 2.0).abs() < 0.1);
+
+        let outside = genes
+            .iter()
+            .filter(|gene| !(1.0..=3.0).contains(*gene))
+            .count();
+        assert!((900..1100).contains(&outside));
+    }
+
+    #[test]
+    fn sbx_is_symmetric_and_tightens_with_eta() {
+        // SBX can land arbitrarily far away, so compare counts rather than
+        // means.
+        let below_mean = cross_gene(&SbxCrossOver::new(2.0), 1.0, 3.0)
+            .iter()
+            .filter(|&&gene| gene < 2.0)
+            .count();
+        assert!((900..1100).contains(&below_mean));
+
+        let near_parents = |eta| {
+            cross_gene(&SbxCrossOver::new(eta), 1.0, 3.0)
+                .iter()
+                .filter(|&&gene| (gene - 1.0).abs() < 0.2 || (gene - 3.0).abs() < 0.2)
+                .count()
+        };
+        // About 18% of children at eta = 0, nearly all at eta = 20.
+        assert!((250..450).contains(&near_parents(0.0)));
+        assert!(near_parents(20.0) > 1800);
+    }
+
+    #[test]
+    fn neuron_crossover_keeps_blocks_whole() {
+        let children = cross_binary(&NeuronCrossOver::new(vec![3, 3, 3, 3]), 12);
+        let mut from_second = 0;
+
+        for child in &children {
+            for block in child.chunks(3) {
+                assert!(block.iter().all(|&gene| gene == block[0]));
+                from_second += block[0] as usize;
+            }
+        }
+
+        assert!((3800..4200).contains(&from_second));
+    }
+
+    #[test]
+    fn arithmetic_keeps_each_gene_kind() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let parent_one: Chromosome<MixedGene> = [
+            MixedGene::Real(1.0),
+            MixedGene::Integer(1),
+            MixedGene::Binary(true),
+        ]
+        .into_iter()
+        .collect();
+        let parent_two: Chromosome<MixedGene> = [
+            MixedGene::Real(2.0),
+            MixedGene::Integer(4),
+            MixedGene::Binary(false),
+        ]
+        .into_iter()
+        .collect();
+
+        let child: Vec<_> = ArithmeticCrossOver::new(0.5)
+            .crossover(&mut rng, &parent_one, &parent_two)
+            .into_iter()
+            .collect();
+
+        assert_eq!(child[0], MixedGene::Real(1.5));
+        // 2.5 rounds away from zero.
+        assert_eq!(child[1], MixedGene::Integer(3));
+        assert!(child[2].binary().is_some());
+    }
 }
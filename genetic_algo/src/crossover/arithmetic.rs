@@ -0,0 +1,92 @@
+use rand::Rng;
+
+use super::Crossover;
+use crate::chromosome::*;
+use crate::gene::MixedGene;
+
+/// Every gene is `alpha * a + (1 - alpha) * b`, so `alpha = 0.5` averages
+/// the parents.
+///
+/// Integer genes are rounded to the nearest integer. In mixed genomes,
+/// binary genes come from the first parent with probability `alpha`.
+#[derive(Clone, Debug)]
+pub struct ArithmeticCrossOver {
+    alpha: f32,
+}
+
+impl ArithmeticCrossOver {
+    pub fn new(alpha: f32) -> Self {
+        assert!((0.0..=1.0).contains(&alpha));
+        Self { alpha }
+    }
+}
+
+impl Crossover for ArithmeticCrossOver {
+    fn crossover(
+        &self,
+        _rng: &mut dyn rand::RngCore,
+        parent_one: &Chromosome,
+        parent_two: &Chromosome,
+    ) -> Chromosome {
+        assert!(parent_one.len() == parent_two.len());
+
+        parent_one
+            .iter()
+            .zip(parent_two.iter())
+            .map(|(&a, &b)| self.blend(a, b))
+            .collect()
+    }
+}
+
+impl Crossover<i32> for ArithmeticCrossOver {
+    fn crossover(
+        &self,
+        _rng: &mut dyn rand::RngCore,
+        parent_one: &Chromosome<i32>,
+        parent_two: &Chromosome<i32>,
+    ) -> Chromosome<i32> {
+        assert!(parent_one.len() == parent_two.len());
+
+        parent_one
+            .iter()
+            .zip(parent_two.iter())
+            .map(|(&a, &b)| self.blend(a as f32, b as f32).round() as i32)
+            .collect()
+    }
+}
+
+impl Crossover<MixedGene> for ArithmeticCrossOver {
+    fn crossover(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        parent_one: &Chromosome<MixedGene>,
+        parent_two: &Chromosome<MixedGene>,
+    ) -> Chromosome<MixedGene> {
+        assert!(parent_one.len() == parent_two.len());
+
+        parent_one
+            .iter()
+            .zip(parent_two.iter())
+            .map(|(&a, &b)| match (a, b) {
+                (MixedGene::Real(a), MixedGene::Real(b)) => MixedGene::Real(self.blend(a, b)),
+                (MixedGene::Integer(a), MixedGene::Integer(b)) => {
+                    MixedGene::Integer(self.blend(a as f32, b as f32).round() as i32)
+                }
+                (MixedGene::Binary(a), MixedGene::Binary(b)) => {
+                    MixedGene::Binary(if rng.gen_bool(self.alpha as f64) {
+                        a
+                    } else {
+                        b
+                    })
+                }
+                _ => panic!("parents have different kinds of genes at the same position"),
+            })
+            .collect()
+    }
+}
+
+impl ArithmeticCrossOver {
+    fn blend(&self, a: f32, b: f32) -> f32 {
+        self.alpha * a + (1.0 - self.alpha) * b
+    }
+}
@@ -0,0 +1,44 @@
+use rand::Rng;
+
+use super::Crossover;
+use crate::chromosome::*;
+
+/// BLX-α: every gene is drawn uniformly from the interval spanned by the
+/// parents' genes, widened by `alpha` times its length on both sides.
+#[derive(Clone, Debug)]
+pub struct BlxCrossOver {
+    alpha: f32,
+}
+
+impl BlxCrossOver {
+    pub fn new(alpha: f32) -> Self {
+        assert!(alpha >= 0.0);
+        Self { alpha }
+    }
+}
+
+impl Crossover for BlxCrossOver {
+    fn crossover(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        parent_one: &Chromosome,
+        parent_two: &Chromosome,
+    ) -> Chromosome {
+        assert!(parent_one.len() == parent_two.len());
+
+        parent_one
+            .iter()
+            .zip(parent_two.iter())
+            .map(|(&a, &b)| {
+                let (low, high) = (a.min(b), a.max(b));
+                let margin = self.alpha * (high - low);
+
+                if margin > 0.0 {
+                    rng.gen_range(low - margin..=high + margin)
+                } else {
+                    low
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,61 @@
+use rand::seq::index;
+
+use super::Crossover;
+use crate::chromosome::*;
+use crate::gene::Gene;
+
+/// Cuts the parents at `k` distinct random points and alternates between
+/// them, starting with the first. `k = 2` is the classic two-point
+/// crossover.
+#[derive(Clone, Debug)]
+pub struct KPointCrossOver {
+    k: usize,
+}
+
+impl KPointCrossOver {
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0);
+        Self { k }
+    }
+}
+
+impl<G> Crossover<G> for KPointCrossOver
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        parent_one: &Chromosome<G>,
+        parent_two: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert!(parent_one.len() == parent_two.len());
+
+        // Cut points lie strictly between genes, so there are len - 1.
+        let cuts = parent_one.len().saturating_sub(1);
+        let mut points: Vec<usize> = index::sample(rng, cuts, self.k.min(cuts))
+            .into_iter()
+            .map(|cut| cut + 1)
+            .collect();
+        points.sort_unstable();
+
+        let mut points = points.into_iter().peekable();
+        let mut from_second = false;
+
+        parent_one
+            .iter()
+            .zip(parent_two.iter())
+            .enumerate()
+            .map(|(i, (&a, &b))| {
+                if points.next_if_eq(&i).is_some() {
+                    from_second = !from_second;
+                }
+                if from_second {
+                    b
+                } else {
+                    a
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,52 @@
+use rand::Rng;
+
+use super::Crossover;
+use crate::chromosome::*;
+use crate::gene::Gene;
+
+/// Layer-aware crossover: treats the chromosome as consecutive per-neuron
+/// blocks (a bias followed by the neuron's weights) and takes every block
+/// whole from one parent or the other, so co-adapted weights stay together.
+#[derive(Clone, Debug)]
+pub struct NeuronCrossOver {
+    /// Length of each block, in chromosome order.
+    blocks: Vec<usize>,
+}
+
+impl NeuronCrossOver {
+    /// `blocks` must add up to the chromosome length; for networks, see
+    /// `lib_neuralnetwork::Network::neuron_blocks`.
+    pub fn new(blocks: Vec<usize>) -> Self {
+        Self { blocks }
+    }
+}
+
+impl<G> Crossover<G> for NeuronCrossOver
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        parent_one: &Chromosome<G>,
+        parent_two: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert!(parent_one.len() == parent_two.len());
+        assert_eq!(self.blocks.iter().sum::<usize>(), parent_one.len());
+
+        let mut genes = parent_one.iter().zip(parent_two.iter());
+
+        self.blocks
+            .iter()
+            .flat_map(|&block| {
+                let from_first = rng.gen_bool(0.5);
+
+                genes
+                    .by_ref()
+                    .take(block)
+                    .map(move |(&a, &b)| if from_first { a } else { b })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
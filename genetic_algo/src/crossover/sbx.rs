@@ -0,0 +1,49 @@
+use rand::Rng;
+
+use super::Crossover;
+use crate::chromosome::*;
+
+/// Simulated binary crossover: spreads every gene around the parents' mean
+/// like single-point crossover spreads bits. A larger distribution index
+/// `eta` keeps children closer to their parents.
+#[derive(Clone, Debug)]
+pub struct SbxCrossOver {
+    eta: f32,
+}
+
+impl SbxCrossOver {
+    pub fn new(eta: f32) -> Self {
+        assert!(eta >= 0.0);
+        Self { eta }
+    }
+}
+
+impl Crossover for SbxCrossOver {
+    /// Returns one of the two SBX children, picked at random per gene.
+    fn crossover(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        parent_one: &Chromosome,
+        parent_two: &Chromosome,
+    ) -> Chromosome {
+        assert!(parent_one.len() == parent_two.len());
+
+        let exponent = 1.0 / (self.eta + 1.0);
+
+        parent_one
+            .iter()
+            .zip(parent_two.iter())
+            .map(|(&a, &b)| {
+                let u: f32 = rng.gen();
+                let beta = if u <= 0.5 {
+                    (2.0 * u).powf(exponent)
+                } else {
+                    (1.0 / (2.0 * (1.0 - u))).powf(exponent)
+                };
+                let sign = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+
+                0.5 * ((a + b) + sign * beta * (a - b))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,37 @@
+use rand::Rng;
+
+use super::Crossover;
+use crate::chromosome::*;
+use crate::gene::Gene;
+
+/// Takes the genes before a random cut point from the first parent and the
+/// rest from the second.
+#[derive(Clone, Debug, Default)]
+pub struct SinglePointCrossOver;
+
+impl<G> Crossover<G> for SinglePointCrossOver
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn rand::RngCore,
+        parent_one: &Chromosome<G>,
+        parent_two: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert!(parent_one.len() == parent_two.len());
+
+        let point = if parent_one.len() < 2 {
+            parent_one.len()
+        } else {
+            rng.gen_range(1..parent_one.len())
+        };
+
+        parent_one
+            .iter()
+            .take(point)
+            .chain(parent_two.iter().skip(point))
+            .copied()
+            .collect()
+    }
+}
@@ -2,17 +2,21 @@ use rand::Rng;
 
 use super::Crossover;
 use crate::chromosome::*;
+use crate::gene::Gene;
 
 #[derive(Clone, Debug, Default)]
 pub struct UniformCrossOver;
 
-impl Crossover for UniformCrossOver {
+impl<G> Crossover<G> for UniformCrossOver
+where
+    G: Gene,
+{
     fn crossover(
         &self,
         rng: &mut dyn rand::RngCore,
-        parent_one: &Chromosome,
-        parent_two: &Chromosome,
-    ) -> Chromosome {
+        parent_one: &Chromosome<G>,
+        parent_two: &Chromosome<G>,
+    ) -> Chromosome<G> {
         assert!(parent_one.len() == parent_two.len());
 
         parent_one
@@ -0,0 +1,117 @@
+use std::fmt::Debug;
+
+use serde::{Deserialize, Serialize};
+
+use crate::MaybeSync;
+
+/// A value a [`Chromosome`](crate::Chromosome) can be made of.
+///
+/// Operators that only move genes around work with any gene: the uniform,
+/// single-point, k-point and neuron crossovers, and
+/// [`CompositeMutation`](crate::CompositeMutation). The others are
+/// implemented for the genes they make sense for:
+///
+/// - `f32`: every other crossover and mutation.
+/// - `i32`: [`ArithmeticCrossOver`](crate::ArithmeticCrossOver) (rounded),
+///   [`CreepMutation`](crate::CreepMutation) and
+///   [`ResetMutation`](crate::ResetMutation).
+/// - `bool`: [`BitFlipMutation`](crate::BitFlipMutation).
+/// - [`MixedGene`]: [`ArithmeticCrossOver`](crate::ArithmeticCrossOver) and
+///   [`MixedMutation`](crate::MixedMutation).
+pub trait Gene: Copy + PartialEq + Debug + MaybeSync {
+    /// The gene as a real number, for distances and statistics; booleans
+    /// are 0 or 1.
+    fn to_f32(self) -> f32;
+    /// The gene of the same kind as `self` closest to `value`, used to
+    /// bring genes back within [`Bounds`](crate::Bounds).
+    fn with_f32(self, value: f32) -> Self;
+    /// Whether the gene is a switch, which [`Bounds`](crate::Bounds) leaves
+    /// alone.
+    fn is_binary(self) -> bool {
+        false
+    }
+}
+
+impl Gene for f32 {
+    fn to_f32(self) -> f32 {
+        self
+    }
+    fn with_f32(self, value: f32) -> Self {
+        value
+    }
+}
+
+impl Gene for i32 {
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+    fn with_f32(self, value: f32) -> Self {
+        value.round() as i32
+    }
+}
+
+impl Gene for bool {
+    fn to_f32(self) -> f32 {
+        if self {
+            1.0
+        } else {
+            0.0
+        }
+    }
+    fn with_f32(self, value: f32) -> Self {
+        value >= 0.5
+    }
+    fn is_binary(self) -> bool {
+        true
+    }
+}
+
+/// Gene of a genome mixing kinds of traits, e.g. network weights alongside
+/// layer sizes and sensor switches. Operators keep each gene's kind.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MixedGene {
+    Real(f32),
+    Integer(i32),
+    Binary(bool),
+}
+
+impl MixedGene {
+    pub fn real(self) -> Option<f32> {
+        match self {
+            Self::Real(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn integer(self) -> Option<i32> {
+        match self {
+            Self::Integer(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn binary(self) -> Option<bool> {
+        match self {
+            Self::Binary(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl Gene for MixedGene {
+    fn to_f32(self) -> f32 {
+        match self {
+            Self::Real(value) => value,
+            Self::Integer(value) => value.to_f32(),
+            Self::Binary(value) => value.to_f32(),
+        }
+    }
+    fn with_f32(self, value: f32) -> Self {
+        match self {
+            Self::Real(gene) => Self::Real(gene.with_f32(value)),
+            Self::Integer(gene) => Self::Integer(gene.with_f32(value)),
+            Self::Binary(gene) => Self::Binary(gene.with_f32(value)),
+        }
+    }
+    fn is_binary(self) -> bool {
+        matches!(self, Self::Binary(_))
+    }
+}
@@ -0,0 +1,160 @@
+use std::cmp::Ordering;
+
+use crate::chromosome::Chromosome;
+use crate::gene::Gene;
+use crate::individual::Individual;
+
+/// The best individuals ever seen across generations, fittest first.
+#[derive(Clone, Debug)]
+pub struct HallOfFame<G = f32> {
+    pub capacity: usize,
+    members: Vec<Champion<G>>,
+    generation: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Champion<G = f32> {
+    pub chromosome: Chromosome<G>,
+    pub fitness: f32,
+    /// Number of [`HallOfFame::update`] calls before the one that inducted
+    /// it.
+    pub generation: usize,
+}
+
+impl<G> HallOfFame<G>
+where
+    G: Gene,
+{
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            members: Vec::new(),
+            generation: 0,
+        }
+    }
+    pub fn members(&self) -> &[Champion<G>] {
+        &self.members
+    }
+    pub fn best(&self) -> Option<&Champion<G>> {
+        self.members.first()
+    }
+    /// Inducts the individuals of `population` fitter than the current
+    /// members. Chromosomes already in the hall aren't inducted twice, but
+    /// their fitness is refreshed, as an individual evaluated again (say, a
+    /// steady-state survivor) may have done better or worse since.
+    pub fn update<I>(&mut self, population: &[I])
+    where
+        I: Individual<G>,
+    {
+        for individual in population {
+            let fitness = individual.fitness();
+
+            if let Some(member) = self
+                .members
+                .iter_mut()
+                .find(|member| &member.chromosome == individual.chromosome())
+            {
+                member.fitness = fitness;
+                self.members
+                    .sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap_or(Ordering::Equal));
+                continue;
+            }
+
+            let full = self.members.len() >= self.capacity;
+
+            if full
+                && self
+                    .members
+                    .last()
+                    .is_none_or(|worst| fitness <= worst.fitness)
+            {
+                continue;
+            }
+
+            let position = self.members.partition_point(|member| {
+                member
+                    .fitness
+                    .partial_cmp(&fitness)
+                    .unwrap_or(Ordering::Equal)
+                    != Ordering::Less
+            });
+            self.members.insert(
+                position,
+                Champion {
+                    chromosome: individual.chromosome().clone(),
+                    fitness,
+                    generation: self.generation,
+                },
+            );
+            self.members.truncate(self.capacity);
+        }
+        self.generation += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestIndividual {
+        chromosome: Chromosome,
+        /// Added to the fitness, so the same chromosome can score
+        /// differently.
+        bonus: f32,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self {
+                chromosome,
+                bonus: 0.0,
+            }
+        }
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().sum::<f32>() + self.bonus
+        }
+    }
+
+    fn population(genes: &[f32]) -> Vec<TestIndividual> {
+        genes
+            .iter()
+            .map(|&gene| TestIndividual::create([gene].into_iter().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_the_best_ever_seen() {
+        let mut hall = HallOfFame::new(3);
+
+        hall.update(&population(&[1.0, 5.0, 3.0, 5.0]));
+        hall.update(&population(&[2.0, 4.0, 0.0]));
+
+        let members: Vec<_> = hall
+            .members()
+            .iter()
+            .map(|champion| (champion.fitness, champion.generation))
+            .collect();
+        assert_eq!(members, vec![(5.0, 0), (4.0, 1), (3.0, 0)]);
+        assert_eq!(hall.best().unwrap().fitness, 5.0);
+    }
+
+    #[test]
+    fn refreshes_members_seen_again() {
+        let mut hall = HallOfFame::new(2);
+        hall.update(&population(&[3.0, 5.0]));
+
+        let mut seen_again = population(&[5.0]);
+        seen_again[0].bonus = -4.0;
+        hall.update(&seen_again);
+
+        let members: Vec<_> = hall
+            .members()
+            .iter()
+            .map(|champion| champion.fitness)
+            .collect();
+        assert_eq!(members, vec![3.0, 1.0]);
+    }
+}
@@ -1,7 +1,26 @@
 use crate::chromosome::Chromosome;
+use crate::lineage::Lineage;
 
-pub trait Individual {
+/// `G` is the type of the genes, see [`Gene`](crate::Gene).
+pub trait Individual<G = f32> {
+    fn create(chromosome: Chromosome<G>) -> Self;
+    fn chromosome(&self) -> &Chromosome<G>;
+    fn fitness(&self) -> f32;
+
+    /// Where the individual comes from. Individuals that don't store the
+    /// lineage [`Individual::set_lineage`] gives them aren't tracked.
+    fn lineage(&self) -> Option<&Lineage> {
+        None
+    }
+    fn set_lineage(&mut self, _lineage: Lineage) {}
+}
+
+/// Individual judged on several objectives at once instead of a single
+/// fitness, for [`Nsga2`](crate::Nsga2).
+pub trait MultiObjectiveIndividual {
     fn create(chromosome: Chromosome) -> Self;
     fn chromosome(&self) -> &Chromosome;
-    fn fitness(&self) -> f32;
+    /// One value per objective, all maximized; negate an objective to
+    /// minimize it. Every individual must report the same number.
+    fn objectives(&self) -> Vec<f32>;
 }
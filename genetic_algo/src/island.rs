@@ -0,0 +1,326 @@
+use std::cmp::Ordering;
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::gene::Gene;
+use crate::individual::Individual;
+use crate::selection::SelectionMethod;
+use crate::statistics::Statistics;
+use crate::{copy, GeneticAlgorithm, MaybeSync};
+
+/// Which islands send their champions where.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MigrationTopology {
+    /// Island `i` sends to island `i + 1`, and the last one to the first.
+    #[default]
+    Ring,
+    /// Every island sends to every other island.
+    FullyConnected,
+}
+
+/// Several populations evolving side by side, each with its own
+/// [`GeneticAlgorithm`], that swap their best individuals every
+/// `interval` generations.
+pub struct IslandModel<S, G = f32> {
+    pub islands: Vec<GeneticAlgorithm<S, G>>,
+    pub topology: MigrationTopology,
+    /// Generations between two migrations.
+    pub interval: usize,
+    /// How many of its best individuals an island sends to each
+    /// destination.
+    pub migrants: usize,
+    since_migration: usize,
+}
+
+impl<S, G> IslandModel<S, G>
+where
+    S: SelectionMethod,
+    G: Gene,
+{
+    /// Interleaves the islands' lineage ids, so they stay unique across
+    /// the whole model.
+    pub fn new(
+        mut islands: Vec<GeneticAlgorithm<S, G>>,
+        topology: MigrationTopology,
+        interval: usize,
+        migrants: usize,
+    ) -> Self {
+        assert!(!islands.is_empty());
+        assert!(interval > 0);
+
+        let count = islands.len() as u64;
+        for (index, island) in islands.iter_mut().enumerate() {
+            island.next_id = index as u64;
+            island.id_step = count;
+        }
+        Self {
+            islands,
+            topology,
+            interval,
+            migrants,
+            since_migration: 0,
+        }
+    }
+
+    /// Evolves `populations[i]` with `islands[i]`, then migrates if this
+    /// generation is due. Migrants are copies of the senders' best
+    /// individuals and replace the receivers' last children, so elites
+    /// (which come first) are kept; migrants that don't fit among the
+    /// children are dropped.
+    pub fn evolve<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        populations: &[Vec<I>],
+    ) -> (Vec<Vec<I>>, Vec<Statistics>)
+    where
+        I: Individual<G> + MaybeSync,
+    {
+        assert_eq!(populations.len(), self.islands.len());
+
+        let (mut new_populations, statistics): (Vec<_>, Vec<_>) = self
+            .islands
+            .iter_mut()
+            .zip(populations)
+            .map(|(island, population)| island.evolve(rng, population))
+            .unzip();
+
+        self.since_migration += 1;
+
+        if self.since_migration >= self.interval {
+            self.since_migration = 0;
+            // Every child is open to migrants, the last ones first.
+            let slots: Vec<Vec<usize>> = self
+                .islands
+                .iter()
+                .zip(&new_populations)
+                .map(|(island, population)| {
+                    let length = population.len();
+                    (island.elitism.count(length).min(length)..length)
+                        .rev()
+                        .collect()
+                })
+                .collect();
+            let emigrants = self.emigrants(populations);
+            self.immigrate(&emigrants, &mut new_populations, &slots);
+        }
+
+        (new_populations, statistics)
+    }
+
+    /// Steady-state counterpart of [`IslandModel::evolve`]: replaces the
+    /// `count` least fit individuals of `populations[i]` in place with
+    /// `islands[i]`'s [`GeneticAlgorithm::replace_worst`], then migrates if
+    /// this round is due. Migrants take the place of some of the new
+    /// children, so elites are kept as well. Returns the replaced indices
+    /// of each population, migrants' included.
+    pub fn replace_worst<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        populations: &mut [Vec<I>],
+        count: usize,
+    ) -> Vec<Vec<usize>>
+    where
+        I: Individual<G> + MaybeSync,
+    {
+        assert_eq!(populations.len(), self.islands.len());
+
+        self.since_migration += 1;
+
+        // Migrants are picked before replacement, while every individual
+        // still has a fitness.
+        let emigrants = if self.since_migration >= self.interval {
+            self.since_migration = 0;
+            Some(self.emigrants(populations))
+        } else {
+            None
+        };
+
+        let replaced: Vec<Vec<usize>> = self
+            .islands
+            .iter_mut()
+            .zip(populations.iter_mut())
+            .map(|(island, population)| island.replace_worst(rng, population, count))
+            .collect();
+
+        if let Some(emigrants) = emigrants {
+            self.immigrate(&emigrants, populations, &replaced);
+        }
+        replaced
+    }
+
+    /// Copies of each island's best individuals.
+    fn emigrants<I>(&self, populations: &[Vec<I>]) -> Vec<Vec<I>>
+    where
+        I: Individual<G>,
+    {
+        populations
+            .iter()
+            .map(|population| {
+                let mut best: Vec<&I> = population.iter().collect();
+                best.sort_by(|a, b| {
+                    b.fitness()
+                        .partial_cmp(&a.fitness())
+                        .unwrap_or(Ordering::Equal)
+                });
+                best.into_iter().take(self.migrants).map(copy).collect()
+            })
+            .collect()
+    }
+
+    /// Sends `emigrants[i]` from island `i` to its neighbours, where they
+    /// replace `receivers[to]` at the indices `slots[to]`, in order.
+    /// Migrants left without a slot are dropped.
+    fn immigrate<I>(&self, emigrants: &[Vec<I>], receivers: &mut [Vec<I>], slots: &[Vec<usize>])
+    where
+        I: Individual<G>,
+    {
+        let count = self.islands.len();
+        if count < 2 {
+            return;
+        }
+
+        for (from, best) in emigrants.iter().enumerate() {
+            let destinations: Vec<usize> = match self.topology {
+                MigrationTopology::Ring => vec![(from + 1) % count],
+                MigrationTopology::FullyConnected => (0..count).filter(|&to| to != from).collect(),
+            };
+
+            for to in destinations {
+                // Each sender fills its own share of the slots, so senders
+                // don't overwrite each other's migrants.
+                let share = match self.topology {
+                    MigrationTopology::Ring => 0,
+                    MigrationTopology::FullyConnected => (from + count - to) % count - 1,
+                };
+
+                for (i, migrant) in best.iter().enumerate() {
+                    let Some(&index) = slots[to].get(share * self.migrants + i) else {
+                        break;
+                    };
+                    receivers[to][index] = copy(migrant);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Chromosome, Elitism, GaussianMutation, RouletteWheel, UniformCrossOver};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().sum()
+        }
+    }
+
+    fn island() -> GeneticAlgorithm<RouletteWheel> {
+        GeneticAlgorithm::new(
+            RouletteWheel,
+            UniformCrossOver,
+            GaussianMutation::new(0.0, 0.0),
+        )
+    }
+
+    /// Island `i` holds only copies of the gene `i + 1`, so every
+    /// individual's origin can be read off its gene.
+    fn populations(islands: usize) -> Vec<Vec<TestIndividual>> {
+        (0..islands)
+            .map(|i| {
+                (0..5)
+                    .map(|_| TestIndividual::create([i as f32 + 1.0].into_iter().collect()))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn origins(population: &[TestIndividual]) -> Vec<f32> {
+        population.iter().map(TestIndividual::fitness).collect()
+    }
+
+    #[test]
+    fn ring_migrates_every_interval() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut model = IslandModel::new(
+            (0..3).map(|_| island()).collect(),
+            MigrationTopology::Ring,
+            2,
+            1,
+        );
+
+        let (first, _) = model.evolve(&mut rng, &populations(3));
+        assert!(first.iter().enumerate().all(|(i, population)| {
+            origins(population)
+                .iter()
+                .all(|&gene| gene == i as f32 + 1.0)
+        }));
+
+        let (second, statistics) = model.evolve(&mut rng, &populations(3));
+        assert_eq!(statistics.len(), 3);
+        assert_eq!(origins(&second[0]), vec![1.0, 1.0, 1.0, 1.0, 3.0]);
+        assert_eq!(origins(&second[1]), vec![2.0, 2.0, 2.0, 2.0, 1.0]);
+        assert_eq!(origins(&second[2]), vec![3.0, 3.0, 3.0, 3.0, 2.0]);
+    }
+
+    #[test]
+    fn fully_connected_sends_to_every_other_island() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut model = IslandModel::new(
+            (0..3).map(|_| island()).collect(),
+            MigrationTopology::FullyConnected,
+            1,
+            1,
+        );
+
+        let (populations, _) = model.evolve(&mut rng, &populations(3));
+
+        for (i, population) in populations.iter().enumerate() {
+            let mut received = origins(&population[3..]);
+            received.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let expected: Vec<f32> = (1..=3)
+                .map(|gene| gene as f32)
+                .filter(|&gene| gene != i as f32 + 1.0)
+                .collect();
+
+            assert_eq!(received, expected);
+        }
+    }
+
+    #[test]
+    fn migrants_never_replace_elites() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        // 3 senders of 2 migrants each, but only 3 children per island.
+        let mut model = IslandModel::new(
+            (0..4)
+                .map(|_| island().with_elitism(Elitism::Count(2)))
+                .collect(),
+            MigrationTopology::FullyConnected,
+            1,
+            2,
+        );
+
+        let (populations, _) = model.evolve(&mut rng, &populations(4));
+
+        for (i, population) in populations.iter().enumerate() {
+            assert_eq!(population.len(), 5);
+            assert_eq!(origins(&population[..2]), vec![i as f32 + 1.0; 2]);
+            assert!(origins(&population[2..])
+                .iter()
+                .all(|&gene| gene != i as f32 + 1.0));
+        }
+    }
+}
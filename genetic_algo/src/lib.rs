@@ -1,55 +1,654 @@
 #![feature(type_alias_impl_trait)]
 pub use self::{
-    chromosome::*, crossover::*, individual::*, mutation::*, selection::*, statistics::*,
+    bounds::*, chromosome::*, crossover::*, gene::*, hall_of_fame::*, individual::*, island::*,
+    lineage::*, mutation::*, novelty::*, nsga2::*, selection::*, speciation::*, statistics::*,
 };
+mod bounds;
 mod chromosome;
 mod crossover;
+mod gene;
+mod hall_of_fame;
 mod individual;
+mod island;
+mod lineage;
 mod mutation;
+pub mod neat;
+mod novelty;
+mod nsga2;
 mod selection;
+mod speciation;
 mod statistics;
 
+use std::cmp::Ordering;
+
 use rand::RngCore;
+#[cfg(feature = "parallel")]
+use rand::SeedableRng;
+#[cfg(feature = "parallel")]
+use rand_chacha::ChaCha8Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// `Send + Sync` with the `parallel` feature, so crossovers, mutations and
+/// individuals can be shared between threads; no bound at all without it.
+#[cfg(feature = "parallel")]
+pub trait MaybeSync: Send + Sync {}
+#[cfg(feature = "parallel")]
+impl<T: Send + Sync + ?Sized> MaybeSync for T {}
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSync {}
+#[cfg(not(feature = "parallel"))]
+impl<T: ?Sized> MaybeSync for T {}
 
-pub struct GeneticAlgorithm<S> {
+/// Evolves chromosomes of `G` genes; see [`Gene`].
+pub struct GeneticAlgorithm<S, G = f32> {
     pub selection_method: S,
-    pub crossover_method: Box<dyn Crossover>,
-    pub mutation_method: Box<dyn Mutation>,
+    pub crossover_method: Box<dyn Crossover<G>>,
+    pub mutation_method: Box<dyn Mutation<G>>,
+    pub elitism: Elitism,
+    /// Breeds each species separately when set.
+    pub speciation: Option<Speciation>,
+    /// Applied to every child after crossover and mutation when set.
+    pub bounds: Option<Bounds>,
+    /// Generations bred so far.
+    generation: usize,
+    /// Next [`Lineage::id`] to hand out, and the gap between two ids, so
+    /// islands hand out disjoint ids.
+    next_id: u64,
+    id_step: u64,
 }
 
-impl<S> GeneticAlgorithm<S>
+/// How many of the fittest individuals [`GeneticAlgorithm::evolve`] copies
+/// into the next generation unchanged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Elitism {
+    Count(usize),
+    /// Fraction of the population, rounded down.
+    Fraction(f32),
+}
+
+impl Default for Elitism {
+    fn default() -> Self {
+        Self::Count(0)
+    }
+}
+
+impl Elitism {
+    /// Number of elites for a population of `size`, never more than `size`.
+    pub fn count(&self, size: usize) -> usize {
+        match *self {
+            Self::Count(count) => count.min(size),
+            Self::Fraction(fraction) => {
+                ((size as f32 * fraction.clamp(0.0, 1.0)).floor() as usize).min(size)
+            }
+        }
+    }
+}
+
+impl<S, G> GeneticAlgorithm<S, G>
 where
     S: SelectionMethod,
+    G: Gene,
 {
     pub fn new(
         selection_method: S,
-        crossover_method: impl Crossover + 'static,
-        mutation_method: impl Mutation + 'static,
+        crossover_method: impl Crossover<G> + 'static,
+        mutation_method: impl Mutation<G> + 'static,
     ) -> Self {
         Self {
             selection_method,
             crossover_method: Box::new(crossover_method),
             mutation_method: Box::new(mutation_method),
+            elitism: Elitism::default(),
+            speciation: None,
+            bounds: None,
+            generation: 0,
+            next_id: 0,
+            id_step: 1,
         }
     }
-    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+    pub fn with_elitism(mut self, elitism: Elitism) -> Self {
+        self.elitism = elitism;
+        self
+    }
+    pub fn with_speciation(mut self, speciation: Speciation) -> Self {
+        self.speciation = Some(speciation);
+        self
+    }
+    pub fn with_bounds(mut self, bounds: Bounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+    /// Number of calls to [`GeneticAlgorithm::evolve`] and
+    /// [`GeneticAlgorithm::replace_worst`] so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+    /// Gives every individual of `population` without a lineage a new one,
+    /// with no parents, born this generation. Call it on the first
+    /// population to track lineages from the start.
+    pub fn found<I>(&mut self, population: &mut [I])
     where
-        I: Individual,
+        I: Individual<G>,
+    {
+        for individual in population {
+            if individual.lineage().is_none() {
+                let lineage = self.new_lineage(Vec::new(), self.generation);
+                individual.set_lineage(lineage);
+            }
+        }
+    }
+    /// Parents are always selected on `rng`. With the `parallel` feature,
+    /// children are then bred across threads, each on its own stream of a
+    /// seed drawn from `rng`, so the result only depends on `rng`, not on
+    /// the number of threads.
+    pub fn evolve<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+    where
+        I: Individual<G> + MaybeSync,
     {
         assert!(!population.is_empty());
-        let new_population = (0..population.len())
-            .map(|_| {
-                let parent_one = self.selection_method.select(rng, population).chromosome();
-                let parent_two = self.selection_method.select(rng, population).chromosome();
 
-                let mut child = self.crossover_method.crossover(rng, parent_one, parent_two);
+        let elite_indices = {
+            let mut indices: Vec<usize> = (0..population.len()).collect();
+            indices.sort_by(|&a, &b| {
+                population[b]
+                    .fitness()
+                    .partial_cmp(&population[a].fitness())
+                    .unwrap_or(Ordering::Equal)
+            });
+            indices.truncate(self.elitism.count(population.len()));
+            indices
+        };
+
+        let mut new_population: Vec<I> = elite_indices
+            .iter()
+            .map(|&index| copy(&population[index]))
+            .collect();
+
+        let children = population.len() - elite_indices.len();
+
+        let parents: Vec<&I> = match &mut self.speciation {
+            None => self
+                .selection_method
+                .select_many(rng, population, 2 * children),
+            Some(speciation) => {
+                speciation.speciate(rng, population);
+                let members = speciation.members();
+                let offspring = speciation.offspring(population, children);
+                let mut parents = Vec::with_capacity(2 * children);
+
+                for (members, count) in members.into_iter().zip(offspring) {
+                    let members: Vec<_> = members
+                        .into_iter()
+                        .map(|index| Member(&population[index]))
+                        .collect();
+
+                    parents.extend(
+                        self.selection_method
+                            .select_many(rng, &members, 2 * count)
+                            .into_iter()
+                            .map(|member| member.0),
+                    );
+                }
+                parents
+            }
+        };
+
+        self.generation += 1;
+        new_population.extend(self.breed_all(rng, &parents));
+
+        let mut statistics = Statistics::analyze(population);
+        statistics.elite_indices = elite_indices;
+        if let Some(speciation) = &self.speciation {
+            statistics.species = speciation.statistics();
+        }
+
+        self.mutation_method.adapt(&statistics);
+
+        (new_population, statistics)
+    }
+    /// Steady-state counterpart of [`GeneticAlgorithm::evolve`]: replaces
+    /// the `count` least fit individuals in place with children of parents
+    /// selected from the whole population, and returns their indices,
+    /// least fit first.
+    ///
+    /// Never replaces the individuals elitism would keep. Ignores
+    /// speciation. Each call counts as a generation: the mutation adapts to
+    /// the statistics of `population` as it was before the replacement,
+    /// and children are born in a new generation for lineages.
+    pub fn replace_worst<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &mut [I],
+        count: usize,
+    ) -> Vec<usize>
+    where
+        I: Individual<G> + MaybeSync,
+    {
+        assert!(!population.is_empty());
+
+        let count = count.min(population.len() - self.elitism.count(population.len()));
+        let mut worst: Vec<usize> = (0..population.len()).collect();
+        worst.sort_by(|&a, &b| {
+            population[a]
+                .fitness()
+                .partial_cmp(&population[b].fitness())
+                .unwrap_or(Ordering::Equal)
+        });
+        worst.truncate(count);
+
+        let statistics = Statistics::analyze(population);
+        self.generation += 1;
+        let children = {
+            let parents = self
+                .selection_method
+                .select_many(rng, population, 2 * count);
+            self.breed_all(rng, &parents)
+        };
+
+        for (&index, child) in worst.iter().zip(children) {
+            population[index] = child;
+        }
+        self.mutation_method.adapt(&statistics);
+
+        worst
+    }
+    /// Breeds one child from each pair of consecutive `parents`, born this
+    /// generation.
+    fn breed_all<I>(&mut self, rng: &mut dyn RngCore, parents: &[&I]) -> Vec<I>
+    where
+        I: Individual<G> + MaybeSync,
+    {
+        let mut children = self.breed_children(rng, parents);
+
+        for (child, parents) in children.iter_mut().zip(parents.chunks(2)) {
+            let mut ids: Vec<u64> = parents
+                .iter()
+                .filter_map(|parent| parent.lineage().map(|lineage| lineage.id))
+                .collect();
+            ids.dedup();
+
+            let lineage = self.new_lineage(ids, self.generation);
+            child.set_lineage(lineage);
+        }
+        children
+    }
+    fn new_lineage(&mut self, parents: Vec<u64>, birth_generation: usize) -> Lineage {
+        let id = self.next_id;
+        self.next_id += self.id_step;
+
+        Lineage {
+            id,
+            parents,
+            birth_generation,
+        }
+    }
+    #[cfg(not(feature = "parallel"))]
+    fn breed_children<I>(&self, rng: &mut dyn RngCore, parents: &[&I]) -> Vec<I>
+    where
+        I: Individual<G> + MaybeSync,
+    {
+        parents
+            .chunks(2)
+            .map(|parents| {
+                breed(
+                    self.crossover(),
+                    self.mutation(),
+                    self.bounds.as_ref(),
+                    rng,
+                    parents[0],
+                    parents[1],
+                )
+            })
+            .collect()
+    }
+    /// Child `i` uses stream `i` of a single seed, whichever thread breeds
+    /// it.
+    #[cfg(feature = "parallel")]
+    fn breed_children<I>(&self, rng: &mut dyn RngCore, parents: &[&I]) -> Vec<I>
+    where
+        I: Individual<G> + MaybeSync,
+    {
+        let seed = rng.next_u64();
+        let (crossover, mutation, bounds) =
+            (self.crossover(), self.mutation(), self.bounds.as_ref());
+
+        parents
+            .par_chunks(2)
+            .enumerate()
+            .map(|(index, parents)| {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                rng.set_stream(index as u64);
+                breed(
+                    crossover, mutation, bounds, &mut rng, parents[0], parents[1],
+                )
+            })
+            .collect()
+    }
+    fn crossover(&self) -> &dyn Crossover<G> {
+        &*self.crossover_method
+    }
+    fn mutation(&self) -> &dyn Mutation<G> {
+        &*self.mutation_method
+    }
+}
+
+fn breed<I, G>(
+    crossover: &dyn Crossover<G>,
+    mutation: &dyn Mutation<G>,
+    bounds: Option<&Bounds>,
+    rng: &mut dyn RngCore,
+    parent_one: &I,
+    parent_two: &I,
+) -> I
+where
+    I: Individual<G>,
+    G: Gene,
+{
+    let mut child = crossover.crossover(rng, parent_one.chromosome(), parent_two.chromosome());
+
+    mutation.mutate(rng, &mut child);
 
-                self.mutation_method.mutate(rng, &mut child);
+    if let Some(bounds) = bounds {
+        bounds.apply(rng, &mut child);
+    }
+
+    I::create(child)
+}
+
+/// An unchanged copy of `individual`, which keeps its lineage.
+pub(crate) fn copy<I, G>(individual: &I) -> I
+where
+    I: Individual<G>,
+    G: Clone,
+{
+    let mut copy = I::create(individual.chromosome().clone());
+    if let Some(lineage) = individual.lineage() {
+        copy.set_lineage(lineage.clone());
+    }
+    copy
+}
+
+/// Lets selection methods pick among a species' members without copying
+/// them out of the population.
+struct Member<'a, I>(&'a I);
+
+impl<I, G> Individual<G> for Member<'_, I>
+where
+    I: Individual<G>,
+{
+    fn create(_: Chromosome<G>) -> Self {
+        unreachable!("selection never creates individuals")
+    }
+    fn chromosome(&self) -> &Chromosome<G> {
+        self.0.chromosome()
+    }
+    fn fitness(&self) -> f32 {
+        self.0.fitness()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().sum()
+        }
+    }
+
+    #[test]
+    fn elites_survive_unchanged() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheel,
+            UniformCrossOver,
+            GaussianMutation::new(1.0, 0.5),
+        )
+        .with_elitism(Elitism::Fraction(0.25));
+        let population: Vec<_> = (0..8)
+            .map(|i| TestIndividual::create(vec![i as f32; 3].into_iter().collect()))
+            .collect();
+
+        let (new_population, statistics) = ga.evolve(&mut rng, &population);
+
+        assert_eq!(statistics.elite_indices(), &[7, 6]);
+        assert_eq!(new_population.len(), 8);
+        assert_eq!(new_population[0].chromosome, population[7].chromosome);
+        assert_eq!(new_population[1].chromosome, population[6].chromosome);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_breeding_ignores_thread_count() {
+        let run = |threads| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let mut ga = GeneticAlgorithm::new(
+                RouletteWheel,
+                UniformCrossOver,
+                GaussianMutation::new(0.5, 0.5),
+            );
+            let population: Vec<_> = (0..64)
+                .map(|i| TestIndividual::create(vec![i as f32; 8].into_iter().collect()))
+                .collect();
+
+            let (new_population, _) = pool.install(|| ga.evolve(&mut rng, &population));
+            new_population
+                .into_iter()
+                .map(|individual| individual.chromosome)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(run(1), run(4));
+    }
 
-                I::create(child)
+    /// Fitness is the number of set bits.
+    struct BitIndividual {
+        chromosome: Chromosome<bool>,
+    }
+
+    impl Individual<bool> for BitIndividual {
+        fn create(chromosome: Chromosome<bool>) -> Self {
+            Self { chromosome }
+        }
+        fn chromosome(&self) -> &Chromosome<bool> {
+            &self.chromosome
+        }
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().filter(|&&bit| bit).count() as f32
+        }
+    }
+
+    #[test]
+    fn evolves_binary_genes() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut ga = GeneticAlgorithm::new(
+            TournamentSelection::new(3),
+            UniformCrossOver,
+            BitFlipMutation::new(0.02),
+        )
+        .with_elitism(Elitism::Count(1));
+        let mut population: Vec<_> = (0..30)
+            .map(|_| BitIndividual::create((0..20).map(|_| rng.gen_bool(0.5)).collect()))
+            .collect();
+
+        let (_, first) = ga.evolve(&mut rng, &population);
+        for _ in 0..30 {
+            population = ga.evolve(&mut rng, &population).0;
+        }
+        let (_, last) = ga.evolve(&mut rng, &population);
+
+        assert!(last.avg_fitness() > first.avg_fitness() + 5.0);
+        assert_eq!(last.best_chromosome().map(Chromosome::len), Some(20));
+    }
+
+    #[test]
+    fn children_respect_bounds() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheel,
+            UniformCrossOver,
+            GaussianMutation::new(1.0, 100.0),
+        )
+        .with_bounds(Bounds::new(BoundPolicy::Reflect).with(3, -1.0..=1.0));
+        let population: Vec<_> = (0..10)
+            .map(|_| TestIndividual::create(vec![0.5; 3].into_iter().collect()))
+            .collect();
+
+        let (new_population, _) = ga.evolve(&mut rng, &population);
+
+        assert!(new_population.iter().all(|individual| individual
+            .chromosome
+            .iter()
+            .all(|gene| (-1.0..=1.0).contains(gene))));
+    }
+
+    #[test]
+    fn composite_steps_adapt() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheel,
+            UniformCrossOver,
+            CompositeMutation::new().with(
+                1.0,
+                ScheduledMutation::new(
+                    1.0,
+                    1.0,
+                    Schedule::Linear {
+                        end: 0.0,
+                        generations: 1,
+                    },
+                ),
+            ),
+        );
+        let zeros: Vec<_> = (0..8)
+            .map(|_| TestIndividual::create(vec![0.0; 4].into_iter().collect()))
+            .collect();
+        let all_zero = |population: &[TestIndividual]| {
+            population
+                .iter()
+                .all(|individual| individual.chromosome.iter().all(|&gene| gene == 0.0))
+        };
+
+        // The schedule reaches a sigma of zero after the first generation.
+        let (first, _) = ga.evolve(&mut rng, &zeros);
+        assert!(!all_zero(&first));
+        let (second, _) = ga.evolve(&mut rng, &zeros);
+        assert!(all_zero(&second));
+    }
+
+    #[test]
+    fn replace_worst_adapts_the_mutation() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheel,
+            UniformCrossOver,
+            ScheduledMutation::new(
+                1.0,
+                1.0,
+                Schedule::Linear {
+                    end: 0.0,
+                    generations: 1,
+                },
+            ),
+        );
+        let zeros = || -> Vec<_> {
+            (0..8)
+                .map(|_| TestIndividual::create(vec![0.0; 4].into_iter().collect()))
+                .collect()
+        };
+        let mutated = |population: &[TestIndividual], replaced: &[usize]| {
+            replaced
+                .iter()
+                .any(|&index| population[index].chromosome.iter().any(|&gene| gene != 0.0))
+        };
+
+        // The schedule reaches a sigma of zero after the first replacement.
+        let mut first = zeros();
+        let replaced = ga.replace_worst(&mut rng, &mut first, 4);
+        assert!(mutated(&first, &replaced));
+
+        let mut second = zeros();
+        let replaced = ga.replace_worst(&mut rng, &mut second, 4);
+        assert!(!mutated(&second, &replaced));
+    }
+
+    #[test]
+    fn replace_worst_spares_the_rest() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheel,
+            UniformCrossOver,
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_elitism(Elitism::Count(4));
+        let mut population: Vec<_> = [3.0, 0.0, 4.0, 1.0, 2.0]
+            .into_iter()
+            .map(|gene| TestIndividual::create([gene].into_iter().collect()))
+            .collect();
+
+        // Elitism keeps four, so only one can go.
+        let replaced = ga.replace_worst(&mut rng, &mut population, 2);
+
+        assert_eq!(replaced, vec![1]);
+        let genes: Vec<_> = population.iter().map(TestIndividual::fitness).collect();
+        assert_eq!(genes[0], 3.0);
+        assert_eq!(&genes[2..], &[4.0, 1.0, 2.0]);
+        assert_ne!(genes[1], 0.0);
+    }
+
+    #[test]
+    fn speciation_keeps_weaker_species_alive() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheel,
+            UniformCrossOver,
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_speciation(Speciation::new(1.0));
+        // One weak cluster of 8 around 1 and one strong cluster of 2 around
+        // 10; fitness is the gene sum.
+        let population: Vec<_> = (0..10)
+            .map(|i| {
+                let gene = if i < 8 { 1.0 } else { 10.0 };
+                TestIndividual::create(vec![gene; 2].into_iter().collect())
             })
             .collect();
 
-        (new_population, Statistics::analyze(population))
+        let (new_population, statistics) = ga.evolve(&mut rng, &population);
+
+        assert_eq!(new_population.len(), 10);
+        assert_eq!(
+            statistics
+                .species
+                .iter()
+                .map(|species| (species.size, species.age, species.best_fitness))
+                .collect::<Vec<_>>(),
+            vec![(8, 1, 2.0), (2, 1, 20.0)]
+        );
+        // Shared fitness is 2 against 20, so the weak species breeds one
+        // child of its own instead of being swamped.
+        let weak = new_population
+            .iter()
+            .filter(|individual| individual.fitness() == 2.0)
+            .count();
+        assert_eq!(weak, 1);
     }
 }
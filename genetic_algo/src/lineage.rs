@@ -0,0 +1,194 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::individual::Individual;
+
+/// Where an individual comes from, as recorded by
+/// [`GeneticAlgorithm`](crate::GeneticAlgorithm).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lineage {
+    /// Unique among the individuals of a run; elites and migrants keep
+    /// theirs.
+    pub id: u64,
+    /// Ids of the parents; empty for founders.
+    pub parents: Vec<u64>,
+    /// Generation the individual was bred in; 0 for founders.
+    pub birth_generation: usize,
+}
+
+/// Family tree of every individual recorded so far, exportable as
+/// GraphViz DOT or JSON.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LineageGraph {
+    nodes: BTreeMap<u64, LineageNode>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LineageNode {
+    pub id: u64,
+    pub parents: Vec<u64>,
+    pub birth_generation: usize,
+    /// Best fitness the individual was recorded with.
+    pub fitness: f32,
+}
+
+impl LineageGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds the evaluated individuals of `population` that have a lineage.
+    pub fn record<I, G>(&mut self, population: &[I])
+    where
+        I: Individual<G>,
+    {
+        for individual in population {
+            let Some(lineage) = individual.lineage() else {
+                continue;
+            };
+            let fitness = individual.fitness();
+
+            self.nodes
+                .entry(lineage.id)
+                .and_modify(|node| node.fitness = node.fitness.max(fitness))
+                .or_insert_with(|| LineageNode {
+                    id: lineage.id,
+                    parents: lineage.parents.clone(),
+                    birth_generation: lineage.birth_generation,
+                    fitness,
+                });
+        }
+    }
+    pub fn get(&self, id: u64) -> Option<&LineageNode> {
+        self.nodes.get(&id)
+    }
+    /// Every recorded node, by id.
+    pub fn nodes(&self) -> impl Iterator<Item = &LineageNode> {
+        self.nodes.values()
+    }
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+    /// `id` and all of its recorded ancestors, e.g. to trace how a
+    /// champion came about without the rest of the population.
+    pub fn ancestry(&self, id: u64) -> Self {
+        let mut seen = BTreeSet::new();
+        let mut pending = vec![id];
+
+        while let Some(id) = pending.pop() {
+            if let Some(node) = self.nodes.get(&id) {
+                if seen.insert(id) {
+                    pending.extend(&node.parents);
+                }
+            }
+        }
+
+        Self {
+            nodes: seen
+                .into_iter()
+                .map(|id| (id, self.nodes[&id].clone()))
+                .collect(),
+        }
+    }
+    /// A `digraph` with one node per individual, labelled with its id,
+    /// birth generation and fitness, and an edge from each recorded parent
+    /// to its child.
+    pub fn write_dot(&self, mut writer: impl io::Write) -> io::Result<()> {
+        writeln!(writer, "digraph lineage {{")?;
+
+        for node in self.nodes.values() {
+            writeln!(
+                writer,
+                "    {} [label=\"{}\\ngeneration {}\\nfitness {}\"];",
+                node.id, node.id, node.birth_generation, node.fitness
+            )?;
+        }
+        for node in self.nodes.values() {
+            for parent in node.parents.iter().filter(|id| self.nodes.contains_key(id)) {
+                writeln!(writer, "    {} -> {};", parent, node.id)?;
+            }
+        }
+        writeln!(writer, "}}")
+    }
+    /// A JSON array of nodes, by id.
+    pub fn write_json(&self, writer: impl io::Write) -> serde_json::Result<()> {
+        serde_json::to_writer_pretty(writer, &self.nodes.values().collect::<Vec<_>>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Chromosome;
+
+    struct TestIndividual {
+        chromosome: Chromosome,
+        lineage: Option<Lineage>,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self {
+                chromosome,
+                lineage: None,
+            }
+        }
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().sum()
+        }
+        fn lineage(&self) -> Option<&Lineage> {
+            self.lineage.as_ref()
+        }
+        fn set_lineage(&mut self, lineage: Lineage) {
+            self.lineage = Some(lineage);
+        }
+    }
+
+    fn individual(id: u64, parents: &[u64], fitness: f32) -> TestIndividual {
+        TestIndividual {
+            chromosome: [fitness].into_iter().collect(),
+            lineage: Some(Lineage {
+                id,
+                parents: parents.to_vec(),
+                birth_generation: parents.len().min(1),
+            }),
+        }
+    }
+
+    #[test]
+    fn ancestry_keeps_only_ancestors() {
+        let mut graph = LineageGraph::new();
+        graph.record(&[individual(0, &[], 1.0), individual(1, &[], 2.0)]);
+        graph.record(&[
+            individual(1, &[], 5.0),
+            individual(2, &[0, 1], 3.0),
+            individual(3, &[1], 0.0),
+        ]);
+
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.get(1).unwrap().fitness, 5.0);
+
+        let ancestry = graph.ancestry(2);
+        let ids: Vec<u64> = ancestry.nodes().map(|node| node.id).collect();
+        assert_eq!(ids, [0, 1, 2]);
+
+        let mut dot = Vec::new();
+        ancestry.write_dot(&mut dot).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+        assert!(dot.starts_with("digraph lineage {"));
+        assert!(dot.contains("0 -> 2;") && dot.contains("1 -> 2;"));
+        assert!(!dot.contains("-> 3;"));
+
+        let mut json = Vec::new();
+        ancestry.write_json(&mut json).unwrap();
+        let nodes: Vec<LineageNode> = serde_json::from_slice(&json).unwrap();
+        assert_eq!(nodes[2].parents, [0, 1]);
+    }
+}
@@ -1,9 +1,248 @@
+mod bit_flip;
+mod cauchy;
+mod composite;
+mod creep;
 mod gaussian;
+mod mixed;
+mod one_fifth;
+mod reset;
+mod scheduled;
+mod self_adaptive;
+mod uniform;
 use rand::RngCore;
 
 use crate::chromosome::Chromosome;
+use crate::statistics::Statistics;
+use crate::MaybeSync;
+pub use bit_flip::BitFlipMutation;
+pub use cauchy::CauchyMutation;
+pub use composite::CompositeMutation;
+pub use creep::CreepMutation;
 pub use gaussian::GaussianMutation;
+pub use mixed::MixedMutation;
+pub use one_fifth::OneFifthRuleMutation;
+pub use reset::ResetMutation;
+pub use scheduled::{Schedule, ScheduledMutation};
+pub use self_adaptive::SelfAdaptiveMutation;
+pub use uniform::UniformMutation;
 
-pub trait Mutation {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
+/// `G` is the type of the genes; see [`Gene`](crate::Gene) for which
+/// mutations support which genes.
+pub trait Mutation<G = f32>: MaybeSync {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>);
+
+    /// Called by [`GeneticAlgorithm::evolve`](crate::GeneticAlgorithm::evolve)
+    /// once per generation with that generation's statistics, so the
+    /// mutation can retune itself. Does nothing by default.
+    fn adapt(&mut self, _statistics: &Statistics) {}
+}
+
+impl<G> Mutation<G> for Box<dyn Mutation<G>> {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        (**self).mutate(rng, child)
+    }
+    fn adapt(&mut self, statistics: &Statistics) {
+        (**self).adapt(statistics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MixedGene;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Mutates a chromosome of 10 000 zeros once.
+    fn mutate_zeros(mutation: &impl Mutation) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut child: Chromosome = vec![0.0; 10_000].into_iter().collect();
+
+        mutation.mutate(&mut rng, &mut child);
+        child.into_iter().collect()
+    }
+
+    fn fraction(genes: &[f32], predicate: impl Fn(f32) -> bool) -> f32 {
+        genes.iter().filter(|&&gene| predicate(gene)).count() as f32 / genes.len() as f32
+    }
+
+    #[test]
+    fn gaussian_is_normally_distributed() {
+        let genes = mutate_zeros(&GaussianMutation::new(1.0, 0.5));
+
+        let mean = genes.iter().sum::<f32>() / genes.len() as f32;
+        let std = (genes.iter().map(|gene| gene * gene).sum::<f32>() / genes.len() as f32).sqrt();
+
+        assert!(mean.abs() < 0.02);
+        assert!((std - 0.5).abs() < 0.02);
+        // 68% within one sigma and some beyond two, unlike uniform noise.
+        assert!((fraction(&genes, |gene| gene.abs() < 0.5) - 0.683).abs() < 0.02);
+        assert!((fraction(&genes, |gene| gene.abs() > 1.0) - 0.046).abs() < 0.01);
+    }
+
+    #[test]
+    fn gaussian_respects_chance() {
+        let genes = mutate_zeros(&GaussianMutation::new(0.1, 0.5));
+
+        assert!((fraction(&genes, |gene| gene != 0.0) - 0.1).abs() < 0.01);
+    }
+
+    #[test]
+    fn cauchy_has_heavy_tails() {
+        let genes = mutate_zeros(&CauchyMutation::new(1.0, 0.5));
+
+        // Half the mass lies within one scale of zero; about 6% lies beyond
+        // ten scales, where a normal distribution has none.
+        assert!((fraction(&genes, |gene| gene.abs() < 0.5) - 0.5).abs() < 0.02);
+        assert!((fraction(&genes, |gene| gene.abs() > 5.0) - 0.063).abs() < 0.01);
+    }
+
+    #[test]
+    fn reset_draws_from_range() {
+        let genes = mutate_zeros(&ResetMutation::new(1.0, 2.0..=3.0));
+
+        assert!(genes.iter().all(|gene| (2.0..=3.0).contains(gene)));
+        assert!((fraction(&genes, |gene| gene < 2.5) - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn discrete_genes_get_discrete_mutations() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        let mut bits: Chromosome<bool> = vec![false; 1000].into_iter().collect();
+        BitFlipMutation::new(0.2).mutate(&mut rng, &mut bits);
+        let flipped = bits.iter().filter(|&&bit| bit).count();
+        assert!((150..250).contains(&flipped));
+
+        let mut integers: Chromosome<i32> = vec![0; 1000].into_iter().collect();
+        CreepMutation::new(1.0, 2).mutate(&mut rng, &mut integers);
+        assert!(integers
+            .iter()
+            .all(|&gene| gene != 0 && (-2..=2).contains(&gene)));
+
+        let mut reset: Chromosome<i32> = vec![0; 1000].into_iter().collect();
+        ResetMutation::new(1.0, 5..=7).mutate(&mut rng, &mut reset);
+        assert!(reset.iter().all(|gene| (5..=7).contains(gene)));
+
+        let mut mixed: Chromosome<MixedGene> = [
+            MixedGene::Real(0.0),
+            MixedGene::Integer(0),
+            MixedGene::Binary(false),
+        ]
+        .into_iter()
+        .collect();
+        MixedMutation::new(1.0, 0.5, 1).mutate(&mut rng, &mut mixed);
+        let mixed: Vec<_> = mixed.into_iter().collect();
+        assert!(mixed[0].real().is_some_and(|gene| gene != 0.0));
+        assert!(mixed[1].integer().is_some_and(|gene| gene.abs() == 1));
+        assert_eq!(mixed[2], MixedGene::Binary(true));
+    }
+
+    #[test]
+    fn composite_applies_each_step_with_its_chance() {
+        let always = mutate_zeros(
+            &CompositeMutation::new()
+                .with(0.0, ResetMutation::new(1.0, 5.0..=5.0))
+                .with(1.0, ResetMutation::new(1.0, 1.0..=1.0)),
+        );
+        assert!(always.iter().all(|&gene| gene == 1.0));
+
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let composite = CompositeMutation::new().with(0.3, ResetMutation::new(1.0, 1.0..=1.0));
+        let mutated = (0..1000)
+            .filter(|_| {
+                let mut child: Chromosome = vec![0.0].into_iter().collect();
+                composite.mutate(&mut rng, &mut child);
+                child.into_iter().next() == Some(1.0)
+            })
+            .count();
+        assert!((250..350).contains(&mutated));
+    }
+
+    #[test]
+    fn schedules_decay_sigma() {
+        let mut linear = ScheduledMutation::new(
+            1.0,
+            1.0,
+            Schedule::Linear {
+                end: 0.2,
+                generations: 4,
+            },
+        );
+        let mut exponential = ScheduledMutation::new(
+            1.0,
+            1.0,
+            Schedule::Exponential {
+                rate: 0.5,
+                min: 0.1,
+            },
+        );
+        let statistics = Statistics::from_fitness([1.0]);
+        let expected = [
+            (1.0, 1.0),
+            (0.8, 0.5),
+            (0.6, 0.25),
+            (0.4, 0.125),
+            (0.2, 0.1),
+            (0.2, 0.1),
+        ];
+
+        for (linear_sigma, exponential_sigma) in expected {
+            assert!((linear.sigma() - linear_sigma).abs() < 1e-6);
+            assert!((exponential.sigma() - exponential_sigma).abs() < 1e-6);
+            linear.adapt(&statistics);
+            exponential.adapt(&statistics);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "end must be >= 0")]
+    fn schedules_reject_negative_sigma() {
+        ScheduledMutation::new(
+            1.0,
+            1.0,
+            Schedule::Linear {
+                end: -1.0,
+                generations: 4,
+            },
+        );
+    }
+
+    #[test]
+    fn one_fifth_rule_follows_success_rate() {
+        let mut mutation = OneFifthRuleMutation::with_window(1.0, 1.0, 0.5, 5);
+
+        // Improving every generation: too successful, so explore more.
+        for fitness in 0..5 {
+            mutation.adapt(&Statistics::from_fitness([fitness as f32]));
+        }
+        assert_eq!(mutation.sigma(), 2.0);
+
+        // Stagnating: narrow the search.
+        for _ in 0..5 {
+            mutation.adapt(&Statistics::from_fitness([0.0]));
+        }
+        assert_eq!(mutation.sigma(), 1.0);
+    }
+
+    #[test]
+    fn self_adaptive_mutates_its_own_sigma() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mutation = SelfAdaptiveMutation::new(1.0, 0.01);
+        let mut sigmas = Vec::new();
+
+        for _ in 0..1000 {
+            let mut child: Chromosome = [0.0, 0.0, 0.0, 0.5].into_iter().collect();
+            mutation.mutate(&mut rng, &mut child);
+
+            let genes: Vec<f32> = child.into_iter().collect();
+            assert!(genes[..3].iter().all(|&gene| gene != 0.0));
+            sigmas.push(genes[3]);
+        }
+
+        // Log-normal around the parent's sigma: half above, half below.
+        let above = sigmas.iter().filter(|&&sigma| sigma > 0.5).count();
+        assert!((450..550).contains(&above));
+        assert!(sigmas.iter().all(|&sigma| sigma >= 0.01));
+    }
 }
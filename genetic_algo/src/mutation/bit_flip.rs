@@ -0,0 +1,27 @@
+use rand::{Rng, RngCore};
+
+use crate::chromosome::Chromosome;
+
+use super::Mutation;
+
+/// Flips each binary gene with probability `chance`.
+pub struct BitFlipMutation {
+    chance: f32,
+}
+
+impl BitFlipMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        Self { chance }
+    }
+}
+
+impl Mutation<bool> for BitFlipMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<bool>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene = !*gene;
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use rand::{Rng, RngCore};
+use rand_distr::Cauchy;
+
+use crate::chromosome::Chromosome;
+
+use super::Mutation;
+
+/// Like [`GaussianMutation`](super::GaussianMutation), but with Cauchy
+/// noise of the given `scale`. Its heavy tails occasionally make large
+/// jumps, which helps escape local optima.
+pub struct CauchyMutation {
+    chance: f32,
+    cauchy: Cauchy<f32>,
+}
+
+impl CauchyMutation {
+    pub fn new(chance: f32, scale: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        Self {
+            chance,
+            cauchy: Cauchy::new(0.0, scale).expect("scale must be > 0"),
+        }
+    }
+}
+
+impl Mutation for CauchyMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene += rng.sample(self.cauchy);
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use rand::{Rng, RngCore};
+
+use crate::chromosome::Chromosome;
+use crate::statistics::Statistics;
+
+use super::Mutation;
+
+/// Runs several mutations in order, each applied to the whole chromosome
+/// with its own probability.
+pub struct CompositeMutation<G = f32> {
+    steps: Vec<(f32, Box<dyn Mutation<G>>)>,
+}
+
+impl<G> Default for CompositeMutation<G> {
+    fn default() -> Self {
+        Self { steps: Vec::new() }
+    }
+}
+
+impl<G> CompositeMutation<G> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Appends `mutation`, run on a child with probability `chance`.
+    pub fn with(mut self, chance: f32, mutation: impl Mutation<G> + 'static) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        self.steps.push((chance, Box::new(mutation)));
+        self
+    }
+}
+
+impl<G> Mutation<G> for CompositeMutation<G> {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        for (chance, mutation) in &self.steps {
+            if rng.gen_bool(*chance as f64) {
+                mutation.mutate(rng, child);
+            }
+        }
+    }
+    fn adapt(&mut self, statistics: &Statistics) {
+        for (_, mutation) in &mut self.steps {
+            mutation.adapt(statistics);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use rand::{Rng, RngCore};
+
+use crate::chromosome::Chromosome;
+
+use super::Mutation;
+
+/// Moves each integer gene with probability `chance` by a non-zero amount
+/// of at most `step` in either direction.
+pub struct CreepMutation {
+    chance: f32,
+    step: i32,
+}
+
+impl CreepMutation {
+    pub fn new(chance: f32, step: i32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(step > 0);
+        Self { chance, step }
+    }
+    pub(crate) fn creep(&self, rng: &mut dyn RngCore, gene: i32) -> i32 {
+        let amount = rng.gen_range(1..=self.step);
+
+        if rng.gen_bool(0.5) {
+            gene.saturating_add(amount)
+        } else {
+            gene.saturating_sub(amount)
+        }
+    }
+}
+
+impl Mutation<i32> for CreepMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<i32>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene = self.creep(rng, *gene);
+            }
+        }
+    }
+}
@@ -1,12 +1,15 @@
 use rand::{Rng, RngCore};
+use rand_distr::Normal;
 
 use crate::chromosome::Chromosome;
 
 use super::Mutation;
 
+/// Adds normally distributed noise with standard deviation `coefficient`
+/// to each gene with probability `chance`.
 pub struct GaussianMutation {
     chance: f32,
-    coefficient: f32,
+    normal: Normal<f32>,
 }
 
 impl GaussianMutation {
@@ -14,7 +17,7 @@ impl GaussianMutation {
         assert!((0.0..=1.0).contains(&chance));
         Self {
             chance,
-            coefficient,
+            normal: Normal::new(0.0, coefficient).expect("coefficient must be finite and >= 0"),
         }
     }
 }
@@ -22,9 +25,8 @@ impl GaussianMutation {
 impl Mutation for GaussianMutation {
     fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
         for gene in child.iter_mut() {
-            let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
             if rng.gen_bool(self.chance as f64) {
-                *gene += sign * self.coefficient * rng.gen::<f32>();
+                *gene += rng.sample(self.normal);
             }
         }
     }
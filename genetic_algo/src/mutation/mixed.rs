@@ -0,0 +1,43 @@
+use rand::{Rng, RngCore};
+use rand_distr::Normal;
+
+use crate::chromosome::Chromosome;
+use crate::gene::MixedGene;
+
+use super::{CreepMutation, Mutation};
+
+/// Mutates each gene of a mixed genome with probability `chance`, the way
+/// that suits its kind: real genes get normal noise with standard
+/// deviation `coefficient`, integer genes creep by at most `step` and
+/// binary genes flip.
+pub struct MixedMutation {
+    chance: f32,
+    normal: Normal<f32>,
+    creep: CreepMutation,
+}
+
+impl MixedMutation {
+    pub fn new(chance: f32, coefficient: f32, step: i32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        Self {
+            chance,
+            normal: Normal::new(0.0, coefficient).expect("coefficient must be finite and >= 0"),
+            creep: CreepMutation::new(1.0, step),
+        }
+    }
+}
+
+impl Mutation<MixedGene> for MixedMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<MixedGene>) {
+        for gene in child.iter_mut() {
+            if !rng.gen_bool(self.chance as f64) {
+                continue;
+            }
+            *gene = match *gene {
+                MixedGene::Real(value) => MixedGene::Real(value + rng.sample(self.normal)),
+                MixedGene::Integer(value) => MixedGene::Integer(self.creep.creep(rng, value)),
+                MixedGene::Binary(value) => MixedGene::Binary(!value),
+            };
+        }
+    }
+}
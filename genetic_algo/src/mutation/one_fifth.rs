@@ -0,0 +1,83 @@
+use rand::{Rng, RngCore};
+use rand_distr::Normal;
+
+use crate::chromosome::Chromosome;
+use crate::statistics::Statistics;
+
+use super::Mutation;
+
+/// Gaussian mutation tuned by Rechenberg's 1/5th success rule.
+///
+/// A generation counts as a success when its best fitness beats every
+/// earlier one. After each `window` generations, σ grows if more than a
+/// fifth of them succeeded and shrinks if fewer did.
+pub struct OneFifthRuleMutation {
+    chance: f32,
+    sigma: f32,
+    /// σ is multiplied by this on too few successes and divided by it on
+    /// too many.
+    factor: f32,
+    window: usize,
+    best: Option<f32>,
+    successes: usize,
+    generations: usize,
+}
+
+impl OneFifthRuleMutation {
+    pub fn new(chance: f32, sigma: f32) -> Self {
+        Self::with_window(chance, sigma, 0.85, 10)
+    }
+    pub fn with_window(chance: f32, sigma: f32, factor: f32, window: usize) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(factor > 0.0 && factor < 1.0);
+        assert!(window > 0);
+        Self {
+            chance,
+            sigma,
+            factor,
+            window,
+            best: None,
+            successes: 0,
+            generations: 0,
+        }
+    }
+    pub fn sigma(&self) -> f32 {
+        self.sigma
+    }
+}
+
+impl Mutation for OneFifthRuleMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let normal = Normal::new(0.0, self.sigma).expect("sigma must be finite and >= 0");
+
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene += rng.sample(normal);
+            }
+        }
+    }
+    fn adapt(&mut self, statistics: &Statistics) {
+        match self.best {
+            Some(best) if statistics.max_fitness <= best => {}
+            // The very first generation has nothing to beat.
+            None => self.best = Some(statistics.max_fitness),
+            Some(_) => {
+                self.successes += 1;
+                self.best = Some(statistics.max_fitness);
+            }
+        }
+        self.generations += 1;
+
+        if self.generations == self.window {
+            let ratio = self.successes as f32 / self.window as f32;
+
+            if ratio > 0.2 {
+                self.sigma /= self.factor;
+            } else if ratio < 0.2 {
+                self.sigma *= self.factor;
+            }
+            self.successes = 0;
+            self.generations = 0;
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::ops::RangeInclusive;
+
+use rand::distributions::uniform::SampleUniform;
+use rand::{Rng, RngCore};
+
+use crate::chromosome::Chromosome;
+use crate::gene::Gene;
+
+use super::Mutation;
+
+/// Replaces each gene with probability `chance` by a fresh value drawn
+/// uniformly from `range`. Works for real and integer genes.
+pub struct ResetMutation<G = f32> {
+    chance: f32,
+    range: RangeInclusive<G>,
+}
+
+impl<G> ResetMutation<G>
+where
+    G: PartialOrd,
+{
+    pub fn new(chance: f32, range: RangeInclusive<G>) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(range.start() <= range.end());
+        Self { chance, range }
+    }
+}
+
+impl<G> Mutation<G> for ResetMutation<G>
+where
+    G: Gene + PartialOrd + SampleUniform,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene = rng.gen_range(self.range.clone());
+            }
+        }
+    }
+}
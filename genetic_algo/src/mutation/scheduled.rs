@@ -0,0 +1,76 @@
+use rand::{Rng, RngCore};
+use rand_distr::Normal;
+
+use crate::chromosome::Chromosome;
+use crate::statistics::Statistics;
+
+use super::Mutation;
+
+/// How [`ScheduledMutation`]'s standard deviation changes per generation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Schedule {
+    /// Straight line from the starting value to `end`, reached after
+    /// `generations`, then constant.
+    Linear { end: f32, generations: usize },
+    /// Multiplied by `rate` every generation, but never below `min`.
+    Exponential { rate: f32, min: f32 },
+}
+
+/// Gaussian mutation whose standard deviation follows a [`Schedule`], so
+/// early generations explore and later ones fine-tune.
+pub struct ScheduledMutation {
+    chance: f32,
+    start: f32,
+    schedule: Schedule,
+    generation: usize,
+}
+
+impl ScheduledMutation {
+    /// `start` and the schedule's `end` or `min` must be >= 0, so sigma
+    /// never goes negative.
+    pub fn new(chance: f32, start: f32, schedule: Schedule) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(start >= 0.0, "start must be >= 0");
+        match schedule {
+            Schedule::Linear { end, .. } => assert!(end >= 0.0, "end must be >= 0"),
+            Schedule::Exponential { min, .. } => assert!(min >= 0.0, "min must be >= 0"),
+        }
+        Self {
+            chance,
+            start,
+            schedule,
+            generation: 0,
+        }
+    }
+    /// Standard deviation for the current generation.
+    pub fn sigma(&self) -> f32 {
+        match self.schedule {
+            Schedule::Linear { end, generations } => {
+                let progress = if generations == 0 {
+                    1.0
+                } else {
+                    (self.generation as f32 / generations as f32).min(1.0)
+                };
+                self.start + (end - self.start) * progress
+            }
+            Schedule::Exponential { rate, min } => {
+                (self.start * rate.powi(self.generation as i32)).max(min)
+            }
+        }
+    }
+}
+
+impl Mutation for ScheduledMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let normal = Normal::new(0.0, self.sigma()).expect("sigma must be finite and >= 0");
+
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene += rng.sample(normal);
+            }
+        }
+    }
+    fn adapt(&mut self, _statistics: &Statistics) {
+        self.generation += 1;
+    }
+}
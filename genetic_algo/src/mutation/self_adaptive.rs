@@ -0,0 +1,45 @@
+use rand::{Rng, RngCore};
+use rand_distr::StandardNormal;
+
+use crate::chromosome::Chromosome;
+
+use super::Mutation;
+
+/// Evolution-strategy mutation: the chromosome's last gene is its own
+/// mutation strength σ, which is mutated first (log-normally) and then
+/// used for the other genes. Good σ values spread along with the genomes
+/// that carry them.
+///
+/// Chromosomes need that extra gene, seeded with the starting σ.
+pub struct SelfAdaptiveMutation {
+    chance: f32,
+    min_sigma: f32,
+}
+
+impl SelfAdaptiveMutation {
+    pub fn new(chance: f32, min_sigma: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(min_sigma >= 0.0);
+        Self { chance, min_sigma }
+    }
+}
+
+impl Mutation for SelfAdaptiveMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let mut genes: Vec<&mut f32> = child.iter_mut().collect();
+        let Some((sigma, genes)) = genes.split_last_mut() else {
+            return;
+        };
+
+        // Usual learning rate for a single step size.
+        let tau = 1.0 / (genes.len().max(1) as f32).sqrt();
+        let noise: f32 = rng.sample(StandardNormal);
+        **sigma = (**sigma * (tau * noise).exp()).max(self.min_sigma);
+
+        for gene in genes {
+            if rng.gen_bool(self.chance as f64) {
+                **gene += **sigma * rng.sample::<f32, _>(StandardNormal);
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+mod genome;
+mod species;
+
+pub use self::{genome::*, species::*};
+
+use std::cmp::Ordering;
+
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use crate::speciation::allocate_offspring;
+use crate::statistics::Statistics;
+
+/// Counterpart of [`Individual`](crate::Individual) for populations evolved
+/// with [`Neat`].
+pub trait NeatIndividual {
+    fn create(genome: NeatGenome) -> Self;
+    fn genome(&self) -> &NeatGenome;
+    fn fitness(&self) -> f32;
+}
+
+#[derive(Clone, Debug)]
+pub struct NeatConfig {
+    pub excess_coefficient: f32,
+    pub disjoint_coefficient: f32,
+    pub weight_coefficient: f32,
+    /// Genomes closer than this to a species' representative join it.
+    pub compatibility_threshold: f32,
+    /// Chance of perturbing each weight and bias of a child.
+    pub weight_mutation_chance: f32,
+    pub weight_mutation_coefficient: f32,
+    pub add_connection_chance: f32,
+    pub add_node_chance: f32,
+    /// Chance a child has two parents rather than being a mutated clone.
+    pub crossover_chance: f32,
+    /// Fraction of each species, best first, allowed to reproduce.
+    pub survival_threshold: f32,
+    /// Species with at least this many members pass their champion on
+    /// unchanged.
+    pub champion_species_size: usize,
+}
+
+impl Default for NeatConfig {
+    fn default() -> Self {
+        Self {
+            excess_coefficient: 1.0,
+            disjoint_coefficient: 1.0,
+            weight_coefficient: 0.4,
+            compatibility_threshold: 3.0,
+            weight_mutation_chance: 0.2,
+            weight_mutation_coefficient: 0.5,
+            add_connection_chance: 0.05,
+            add_node_chance: 0.03,
+            crossover_chance: 0.75,
+            survival_threshold: 0.2,
+            champion_species_size: 5,
+        }
+    }
+}
+
+/// NEAT evolution: speciates the population by compatibility distance,
+/// shares fitness within each species and breeds every species in
+/// proportion to its shared fitness.
+///
+/// Unlike [`GeneticAlgorithm`](crate::GeneticAlgorithm) it keeps state
+/// between generations: the innovation history and the species.
+#[derive(Clone, Debug)]
+pub struct Neat {
+    pub config: NeatConfig,
+    tracker: InnovationTracker,
+    species: Vec<Species>,
+    next_species_id: usize,
+}
+
+impl Neat {
+    /// `inputs` and `outputs` must match the genomes being evolved, which
+    /// should start out as [`NeatGenome::minimal`].
+    pub fn new(config: NeatConfig, inputs: usize, outputs: usize) -> Self {
+        Self {
+            config,
+            tracker: InnovationTracker::new(inputs, outputs),
+            species: Vec::new(),
+            next_species_id: 0,
+        }
+    }
+
+    /// Species found during the last call to [`Neat::evolve`].
+    pub fn species(&self) -> &[Species] {
+        &self.species
+    }
+
+    pub fn evolve<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+    where
+        I: NeatIndividual,
+    {
+        assert!(!population.is_empty());
+
+        self.speciate(rng, population);
+        let offspring = self.allocate_offspring(population);
+
+        let Self {
+            config,
+            tracker,
+            species,
+            ..
+        } = self;
+        let fitness = |index: usize| population[index].fitness();
+        let mut new_population = Vec::with_capacity(population.len());
+
+        for (species, mut count) in species.iter().zip(offspring) {
+            if count == 0 {
+                continue;
+            }
+
+            let mut members = species.members().to_vec();
+            members.sort_by(|&a, &b| {
+                fitness(b)
+                    .partial_cmp(&fitness(a))
+                    .unwrap_or(Ordering::Equal)
+            });
+
+            if members.len() >= config.champion_species_size {
+                new_population.push(I::create(population[members[0]].genome().clone()));
+                count -= 1;
+            }
+
+            let survivors = ((members.len() as f32 * config.survival_threshold).ceil() as usize)
+                .clamp(1, members.len());
+            let survivors = &members[..survivors];
+
+            for _ in 0..count {
+                let first = *survivors.choose(rng).expect("species are never empty");
+
+                let mut child =
+                    if survivors.len() > 1 && rng.gen_bool(config.crossover_chance as f64) {
+                        let second = *survivors.choose(rng).expect("species are never empty");
+                        let (fitter, other) = if fitness(first) >= fitness(second) {
+                            (first, second)
+                        } else {
+                            (second, first)
+                        };
+
+                        NeatGenome::crossover(
+                            rng,
+                            population[fitter].genome(),
+                            population[other].genome(),
+                        )
+                    } else {
+                        population[first].genome().clone()
+                    };
+
+                child.mutate_weights(
+                    rng,
+                    config.weight_mutation_chance,
+                    config.weight_mutation_coefficient,
+                );
+                if rng.gen_bool(config.add_node_chance as f64) {
+                    child.mutate_add_node(rng, tracker);
+                }
+                if rng.gen_bool(config.add_connection_chance as f64) {
+                    child.mutate_add_connection(rng, tracker);
+                }
+
+                new_population.push(I::create(child));
+            }
+        }
+
+        (
+            new_population,
+            Statistics::from_fitness(population.iter().map(|individual| individual.fitness())),
+        )
+    }
+
+    /// Assigns every individual to the first species whose representative
+    /// is compatible with it, founding new species as needed, then picks
+    /// each species' representative for the next generation.
+    fn speciate<I>(&mut self, rng: &mut dyn RngCore, population: &[I])
+    where
+        I: NeatIndividual,
+    {
+        let config = &self.config;
+
+        for species in &mut self.species {
+            species.members_mut().clear();
+        }
+
+        for (index, individual) in population.iter().enumerate() {
+            let genome = individual.genome();
+            let compatible = self.species.iter_mut().find(|species| {
+                genome.distance(
+                    species.representative(),
+                    config.excess_coefficient,
+                    config.disjoint_coefficient,
+                    config.weight_coefficient,
+                ) < config.compatibility_threshold
+            });
+
+            match compatible {
+                Some(species) => species.members_mut().push(index),
+                None => {
+                    let mut species = Species::new(self.next_species_id, genome.clone());
+                    species.members_mut().push(index);
+                    self.species.push(species);
+                    self.next_species_id += 1;
+                }
+            }
+        }
+
+        self.species.retain(|species| !species.is_empty());
+
+        for species in &mut self.species {
+            species.age += 1;
+
+            let best = species
+                .members()
+                .iter()
+                .map(|&index| population[index].fitness())
+                .fold(f32::MIN, f32::max);
+            species.best_fitness = species.best_fitness.max(best);
+
+            let representative = *species
+                .members()
+                .choose(rng)
+                .expect("empty species were removed");
+            species.set_representative(population[representative].genome().clone());
+        }
+    }
+
+    /// Explicit fitness sharing: each member's fitness is divided by its
+    /// species' size, so a species earns offspring in proportion to its mean
+    /// fitness.
+    fn allocate_offspring<I>(&self, population: &[I]) -> Vec<usize>
+    where
+        I: NeatIndividual,
+    {
+        let shares: Vec<f32> = self
+            .species
+            .iter()
+            .map(|species| {
+                species
+                    .members()
+                    .iter()
+                    .map(|&index| population[index].fitness().max(0.0))
+                    .sum::<f32>()
+                    / species.len() as f32
+            })
+            .collect();
+        let sizes: Vec<usize> = self.species.iter().map(Species::len).collect();
+
+        allocate_offspring(&shares, &sizes, population.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    struct TestIndividual {
+        genome: NeatGenome,
+        fitness: f32,
+    }
+
+    impl NeatIndividual for TestIndividual {
+        fn create(genome: NeatGenome) -> Self {
+            Self {
+                genome,
+                fitness: 0.0,
+            }
+        }
+        fn genome(&self) -> &NeatGenome {
+            &self.genome
+        }
+        fn fitness(&self) -> f32 {
+            self.fitness
+        }
+    }
+
+    #[test]
+    fn evolve_keeps_population_size_and_grows_structure() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut neat = Neat::new(
+            NeatConfig {
+                add_node_chance: 0.5,
+                add_connection_chance: 0.5,
+                ..Default::default()
+            },
+            3,
+            2,
+        );
+        let mut population: Vec<_> = (0..30)
+            .map(|_| TestIndividual::create(NeatGenome::minimal(&mut rng, 3, 2)))
+            .collect();
+
+        for _ in 0..10 {
+            for individual in &mut population {
+                individual.fitness = individual.genome.connections().len() as f32;
+            }
+            population = neat.evolve(&mut rng, &population).0;
+            assert_eq!(population.len(), 30);
+        }
+
+        assert!(!neat.species().is_empty());
+        assert!(population
+            .iter()
+            .any(|individual| individual.genome.nodes().len() > 5));
+    }
+}
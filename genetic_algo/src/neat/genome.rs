@@ -0,0 +1,441 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeKind {
+    Input,
+    Output,
+    Hidden,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeGene {
+    pub id: usize,
+    pub kind: NodeKind,
+    pub bias: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConnectionGene {
+    pub innovation: usize,
+    pub from: usize,
+    pub to: usize,
+    pub weight: f32,
+    pub enabled: bool,
+}
+
+/// Hands out innovation numbers and hidden node ids, so the same structural
+/// mutation gets the same numbers in every genome that makes it.
+#[derive(Clone, Debug)]
+pub struct InnovationTracker {
+    connections: HashMap<(usize, usize), usize>,
+    /// Hidden node created by splitting the connection with this innovation.
+    splits: HashMap<usize, usize>,
+    next_innovation: usize,
+    next_node: usize,
+}
+
+impl InnovationTracker {
+    /// Registers the connections of [`NeatGenome::minimal`] for a genome of
+    /// this shape.
+    pub fn new(inputs: usize, outputs: usize) -> Self {
+        let mut tracker = Self {
+            connections: HashMap::new(),
+            splits: HashMap::new(),
+            next_innovation: 0,
+            next_node: inputs + outputs,
+        };
+
+        for from in 0..inputs {
+            for to in inputs..inputs + outputs {
+                tracker.connection(from, to);
+            }
+        }
+        tracker
+    }
+
+    fn connection(&mut self, from: usize, to: usize) -> usize {
+        let next_innovation = &mut self.next_innovation;
+
+        *self.connections.entry((from, to)).or_insert_with(|| {
+            *next_innovation += 1;
+            *next_innovation - 1
+        })
+    }
+
+    fn split(&mut self, innovation: usize) -> usize {
+        let next_node = &mut self.next_node;
+
+        *self.splits.entry(innovation).or_insert_with(|| {
+            *next_node += 1;
+            *next_node - 1
+        })
+    }
+
+    fn fresh_node(&mut self) -> usize {
+        self.next_node += 1;
+        self.next_node - 1
+    }
+}
+
+/// NEAT genome: a graph of neurons whose structure evolves along with its
+/// weights.
+///
+/// Node ids `0..inputs` are the inputs and `inputs..inputs + outputs` the
+/// outputs; hidden nodes get ids from an [`InnovationTracker`]. Connections
+/// are kept sorted by innovation number and never form a cycle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NeatGenome {
+    inputs: usize,
+    outputs: usize,
+    nodes: Vec<NodeGene>,
+    connections: Vec<ConnectionGene>,
+}
+
+impl NeatGenome {
+    /// Every input connected to every output with a random weight, numbered
+    /// the same way as [`InnovationTracker::new`].
+    pub fn minimal(rng: &mut dyn RngCore, inputs: usize, outputs: usize) -> Self {
+        let nodes = (0..inputs)
+            .map(|id| NodeGene {
+                id,
+                kind: NodeKind::Input,
+                bias: 0.0,
+            })
+            .chain((inputs..inputs + outputs).map(|id| NodeGene {
+                id,
+                kind: NodeKind::Output,
+                bias: rng.gen_range(-1.0..=1.0),
+            }))
+            .collect();
+
+        let connections = (0..inputs)
+            .flat_map(|from| (inputs..inputs + outputs).map(move |to| (from, to)))
+            .enumerate()
+            .map(|(innovation, (from, to))| ConnectionGene {
+                innovation,
+                from,
+                to,
+                weight: rng.gen_range(-1.0..=1.0),
+                enabled: true,
+            })
+            .collect();
+
+        Self {
+            inputs,
+            outputs,
+            nodes,
+            connections,
+        }
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+    pub fn outputs(&self) -> usize {
+        self.outputs
+    }
+    pub fn nodes(&self) -> &[NodeGene] {
+        &self.nodes
+    }
+    pub fn connections(&self) -> &[ConnectionGene] {
+        &self.connections
+    }
+
+    /// Perturbs each weight and bias with probability `chance` by up to
+    /// `coefficient` in either direction.
+    pub fn mutate_weights(&mut self, rng: &mut dyn RngCore, chance: f32, coefficient: f32) {
+        let weights = self
+            .connections
+            .iter_mut()
+            .map(|connection| &mut connection.weight)
+            .chain(
+                self.nodes
+                    .iter_mut()
+                    .filter(|node| node.kind != NodeKind::Input)
+                    .map(|node| &mut node.bias),
+            );
+
+        for weight in weights {
+            if rng.gen_bool(chance as f64) {
+                *weight += coefficient * rng.gen_range(-1.0..=1.0);
+            }
+        }
+    }
+
+    /// Connects two so-far unconnected nodes, unless that would create a
+    /// cycle. Returns `false` if no such pair exists.
+    pub fn mutate_add_connection(
+        &mut self,
+        rng: &mut dyn RngCore,
+        tracker: &mut InnovationTracker,
+    ) -> bool {
+        let existing: HashSet<_> = self
+            .connections
+            .iter()
+            .map(|connection| (connection.from, connection.to))
+            .collect();
+
+        let candidates: Vec<_> = self
+            .nodes
+            .iter()
+            .filter(|from| from.kind != NodeKind::Output)
+            .flat_map(|from| {
+                self.nodes
+                    .iter()
+                    .filter(|to| to.kind != NodeKind::Input)
+                    .map(move |to| (from.id, to.id))
+            })
+            .filter(|&(from, to)| {
+                from != to && !existing.contains(&(from, to)) && !self.reaches(to, from)
+            })
+            .collect();
+
+        let Some(&(from, to)) = candidates.choose(rng) else {
+            return false;
+        };
+
+        self.insert_connection(ConnectionGene {
+            innovation: tracker.connection(from, to),
+            from,
+            to,
+            weight: rng.gen_range(-1.0..=1.0),
+            enabled: true,
+        });
+        true
+    }
+
+    /// Splits an enabled connection in two with a new hidden node in
+    /// between. Returns `false` if there is no enabled connection.
+    pub fn mutate_add_node(
+        &mut self,
+        rng: &mut dyn RngCore,
+        tracker: &mut InnovationTracker,
+    ) -> bool {
+        let enabled: Vec<_> = (0..self.connections.len())
+            .filter(|&i| self.connections[i].enabled)
+            .collect();
+
+        let Some(&split) = enabled.choose(rng) else {
+            return false;
+        };
+
+        let old = self.connections[split].clone();
+        self.connections[split].enabled = false;
+
+        let mut node = tracker.split(old.innovation);
+        if self.nodes.iter().any(|existing| existing.id == node) {
+            // Inherited the node from a parent that split this same
+            // connection, but also an enabled copy of the connection.
+            node = tracker.fresh_node();
+        }
+
+        self.nodes.push(NodeGene {
+            id: node,
+            kind: NodeKind::Hidden,
+            bias: 0.0,
+        });
+        self.insert_connection(ConnectionGene {
+            innovation: tracker.connection(old.from, node),
+            from: old.from,
+            to: node,
+            weight: 1.0,
+            enabled: true,
+        });
+        self.insert_connection(ConnectionGene {
+            innovation: tracker.connection(node, old.to),
+            from: node,
+            to: old.to,
+            weight: old.weight,
+            enabled: true,
+        });
+        true
+    }
+
+    /// Lines both parents' connections up by innovation number: matching
+    /// genes come from either parent at random, disjoint and excess ones
+    /// from `fitter` only.
+    pub fn crossover(rng: &mut dyn RngCore, fitter: &Self, other: &Self) -> Self {
+        let other_connections: HashMap<_, _> = other
+            .connections
+            .iter()
+            .map(|connection| (connection.innovation, connection))
+            .collect();
+        let other_nodes: HashMap<_, _> = other.nodes.iter().map(|node| (node.id, node)).collect();
+
+        let connections = fitter
+            .connections
+            .iter()
+            .map(|gene| match other_connections.get(&gene.innovation) {
+                Some(&other_gene) => {
+                    let mut child = if rng.gen_bool(0.5) {
+                        gene.clone()
+                    } else {
+                        other_gene.clone()
+                    };
+                    if !gene.enabled || !other_gene.enabled {
+                        child.enabled = !rng.gen_bool(0.75);
+                    }
+                    child
+                }
+                None => gene.clone(),
+            })
+            .collect();
+
+        let nodes = fitter
+            .nodes
+            .iter()
+            .map(|node| match other_nodes.get(&node.id) {
+                Some(&other_node) if rng.gen_bool(0.5) => other_node.clone(),
+                _ => node.clone(),
+            })
+            .collect();
+
+        Self {
+            inputs: fitter.inputs,
+            outputs: fitter.outputs,
+            nodes,
+            connections,
+        }
+    }
+
+    /// Compatibility distance `c1 * E / N + c2 * D / N + c3 * W`, where `E`
+    /// and `D` count excess and disjoint connections, `W` is the mean weight
+    /// difference of matching ones and `N` the size of the larger genome
+    /// (1 for genomes under 20 connections).
+    pub fn distance(&self, other: &Self, excess: f32, disjoint: f32, weight: f32) -> f32 {
+        let (mut i, mut j) = (0, 0);
+        let (mut matching, mut disjoint_genes, mut weight_difference) = (0, 0, 0.0);
+        let (a, b) = (&self.connections, &other.connections);
+
+        while i < a.len() && j < b.len() {
+            match a[i].innovation.cmp(&b[j].innovation) {
+                std::cmp::Ordering::Equal => {
+                    matching += 1;
+                    weight_difference += (a[i].weight - b[j].weight).abs();
+                    i += 1;
+                    j += 1;
+                }
+                std::cmp::Ordering::Less => {
+                    disjoint_genes += 1;
+                    i += 1;
+                }
+                std::cmp::Ordering::Greater => {
+                    disjoint_genes += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        let excess_genes = (a.len() - i) + (b.len() - j);
+        let size = a.len().max(b.len());
+        let size = if size < 20 { 1.0 } else { size as f32 };
+        let mean_weight_difference = if matching > 0 {
+            weight_difference / matching as f32
+        } else {
+            0.0
+        };
+
+        excess * excess_genes as f32 / size
+            + disjoint * disjoint_genes as f32 / size
+            + weight * mean_weight_difference
+    }
+
+    /// Whether `to` can be reached from `from` following connections,
+    /// enabled or not, since crossover may re-enable them.
+    fn reaches(&self, from: usize, to: usize) -> bool {
+        let mut stack = vec![from];
+        let mut seen = HashSet::new();
+
+        while let Some(node) = stack.pop() {
+            if node == to {
+                return true;
+            }
+            if seen.insert(node) {
+                stack.extend(
+                    self.connections
+                        .iter()
+                        .filter(|connection| connection.from == node)
+                        .map(|connection| connection.to),
+                );
+            }
+        }
+        false
+    }
+
+    fn insert_connection(&mut self, connection: ConnectionGene) {
+        let index = self
+            .connections
+            .partition_point(|existing| existing.innovation < connection.innovation);
+
+        self.connections.insert(index, connection);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn structural_mutations_share_innovation_numbers() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut tracker = InnovationTracker::new(2, 1);
+        let mut a = NeatGenome::minimal(&mut rng, 2, 1);
+        let mut b = a.clone();
+
+        // Splitting the same connection in two genomes must yield the same
+        // node and innovation numbers.
+        assert!(a.mutate_add_node(&mut ChaCha8Rng::seed_from_u64(1), &mut tracker));
+        assert!(b.mutate_add_node(&mut ChaCha8Rng::seed_from_u64(1), &mut tracker));
+
+        let innovations = |genome: &NeatGenome| {
+            genome
+                .connections()
+                .iter()
+                .map(|connection| connection.innovation)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(innovations(&a), innovations(&b));
+        assert_eq!(a.distance(&b, 1.0, 1.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn add_connection_never_creates_a_cycle() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let mut tracker = InnovationTracker::new(3, 2);
+        let mut genome = NeatGenome::minimal(&mut rng, 3, 2);
+
+        for _ in 0..50 {
+            if rng.gen_bool(0.5) {
+                genome.mutate_add_node(&mut rng, &mut tracker);
+            } else {
+                genome.mutate_add_connection(&mut rng, &mut tracker);
+            }
+        }
+
+        for connection in genome.connections() {
+            assert!(!genome.reaches(connection.to, connection.from));
+        }
+        assert!(genome
+            .connections()
+            .windows(2)
+            .all(|pair| pair[0].innovation < pair[1].innovation));
+    }
+
+    #[test]
+    fn crossover_keeps_only_fitter_parents_structure() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let mut tracker = InnovationTracker::new(2, 1);
+        let fitter = NeatGenome::minimal(&mut rng, 2, 1);
+        let mut other = fitter.clone();
+        other.mutate_add_node(&mut rng, &mut tracker);
+
+        let child = NeatGenome::crossover(&mut rng, &fitter, &other);
+
+        assert_eq!(child.nodes().len(), fitter.nodes().len());
+        assert_eq!(child.connections().len(), fitter.connections().len());
+    }
+}
@@ -0,0 +1,46 @@
+use super::NeatGenome;
+
+/// Group of genomes within the compatibility threshold of a representative.
+#[derive(Clone, Debug)]
+pub struct Species {
+    pub id: usize,
+    /// Generations this species has existed for.
+    pub age: usize,
+    /// Best raw fitness any member ever reached.
+    pub best_fitness: f32,
+    representative: NeatGenome,
+    /// Indices into the population currently being evolved.
+    members: Vec<usize>,
+}
+
+impl Species {
+    pub(crate) fn new(id: usize, representative: NeatGenome) -> Self {
+        Self {
+            id,
+            age: 0,
+            best_fitness: f32::MIN,
+            representative,
+            members: Vec::new(),
+        }
+    }
+
+    pub fn representative(&self) -> &NeatGenome {
+        &self.representative
+    }
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub(crate) fn members(&self) -> &[usize] {
+        &self.members
+    }
+    pub(crate) fn members_mut(&mut self) -> &mut Vec<usize> {
+        &mut self.members
+    }
+    pub(crate) fn set_representative(&mut self, representative: NeatGenome) {
+        self.representative = representative;
+    }
+}
@@ -0,0 +1,126 @@
+use std::cmp::Ordering;
+
+/// What an individual did, as opposed to how well it did, e.g. where it
+/// ended up or which places it visited.
+pub trait Behavior {
+    /// Every individual must describe its behavior with the same number of
+    /// values.
+    fn behavior(&self) -> Vec<f32>;
+}
+
+/// Scores individuals by how different their behavior is from the rest
+/// of the population and from an archive of past novel behaviors, which
+/// rewards exploration where fitness alone would be deceptive.
+///
+/// The archive persists between generations.
+#[derive(Clone, Debug)]
+pub struct NoveltySearch {
+    /// Number of nearest neighbours novelty is averaged over.
+    pub k: usize,
+    /// Behaviors at least this novel are added to the archive.
+    pub threshold: f32,
+    archive: Vec<Vec<f32>>,
+}
+
+impl NoveltySearch {
+    pub fn new(k: usize, threshold: f32) -> Self {
+        assert!(k > 0);
+        Self {
+            k,
+            threshold,
+            archive: Vec::new(),
+        }
+    }
+    pub fn archive(&self) -> &[Vec<f32>] {
+        &self.archive
+    }
+    /// Returns the novelty of each individual, in order, then archives the
+    /// behaviors that reached the threshold.
+    pub fn evaluate<I>(&mut self, population: &[I]) -> Vec<f32>
+    where
+        I: Behavior,
+    {
+        let behaviors: Vec<_> = population
+            .iter()
+            .map(|individual| individual.behavior())
+            .collect();
+
+        let scores: Vec<_> = behaviors
+            .iter()
+            .enumerate()
+            .map(|(index, behavior)| {
+                let neighbours = behaviors
+                    .iter()
+                    .enumerate()
+                    .filter(|&(other, _)| other != index)
+                    .map(|(_, other)| other)
+                    .chain(&self.archive);
+
+                novelty(behavior, neighbours, self.k)
+            })
+            .collect();
+
+        for (behavior, &score) in behaviors.into_iter().zip(&scores) {
+            if score >= self.threshold {
+                self.archive.push(behavior);
+            }
+        }
+        scores
+    }
+}
+
+/// Mean Euclidean distance from `behavior` to its `k` nearest
+/// `neighbours`, or to all of them if there are fewer; zero without any.
+pub fn novelty<'a>(
+    behavior: &[f32],
+    neighbours: impl IntoIterator<Item = &'a Vec<f32>>,
+    k: usize,
+) -> f32 {
+    let mut distances: Vec<f32> = neighbours
+        .into_iter()
+        .map(|other| {
+            behavior
+                .iter()
+                .zip(other)
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f32>()
+                .sqrt()
+        })
+        .collect();
+
+    distances.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    distances.truncate(k);
+
+    if distances.is_empty() {
+        0.0
+    } else {
+        distances.iter().sum::<f32>() / distances.len() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Behavior for [f32; 2] {
+        fn behavior(&self) -> Vec<f32> {
+            self.to_vec()
+        }
+    }
+
+    #[test]
+    fn outliers_are_most_novel_and_get_archived() {
+        let mut search = NoveltySearch::new(2, 5.0);
+        let population = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [10.0, 0.0]];
+
+        let scores = search.evaluate(&population);
+
+        assert_eq!(scores[0], 1.0);
+        assert!(scores[3] > scores[1]);
+        assert_eq!(search.archive(), &[vec![10.0, 0.0]]);
+
+        // The archived behavior now makes a repeat of it unoriginal.
+        let scores = search.evaluate(&[[10.0, 0.0], [0.0, 0.0]]);
+        assert_eq!(scores[0], 5.0);
+    }
+}
@@ -0,0 +1,407 @@
+use std::cmp::Ordering;
+use std::io;
+
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::bounds::Bounds;
+use crate::chromosome::Chromosome;
+use crate::crossover::Crossover;
+use crate::individual::MultiObjectiveIndividual;
+use crate::mutation::Mutation;
+
+/// Deb et al.'s NSGA-II: ranks solutions by non-dominated sorting, breaks
+/// ties by crowding distance, and lets each generation's survivors compete
+/// with their offspring.
+pub struct Nsga2 {
+    pub crossover_method: Box<dyn Crossover>,
+    pub mutation_method: Box<dyn Mutation>,
+    /// Applied to every child after crossover and mutation when set.
+    pub bounds: Option<Bounds>,
+    /// Last generation's survivors, which the next evaluated offspring
+    /// compete with.
+    parents: Vec<(Chromosome, Vec<f32>)>,
+}
+
+impl Nsga2 {
+    pub fn new(
+        crossover_method: impl Crossover + 'static,
+        mutation_method: impl Mutation + 'static,
+    ) -> Self {
+        Self {
+            crossover_method: Box::new(crossover_method),
+            mutation_method: Box::new(mutation_method),
+            bounds: None,
+            parents: Vec::new(),
+        }
+    }
+    pub fn with_bounds(mut self, bounds: Bounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+    /// Picks as many survivors as `population` holds among it and the
+    /// previous call's survivors, then breeds them into the next population
+    /// with binary tournaments on rank and crowding distance.
+    ///
+    /// Unlike [`GeneticAlgorithm`](crate::GeneticAlgorithm), never calls
+    /// [`Mutation::adapt`], as there is no single fitness to adapt to.
+    pub fn evolve<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, ParetoStatistics)
+    where
+        I: MultiObjectiveIndividual,
+    {
+        assert!(!population.is_empty());
+
+        let size = population.len();
+        let evaluated: Vec<_> = population
+            .iter()
+            .map(|individual| individual.objectives())
+            .collect();
+
+        let (mut chromosomes, mut objectives): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.parents).into_iter().unzip();
+        chromosomes.extend(
+            population
+                .iter()
+                .map(|individual| individual.chromosome().clone()),
+        );
+        objectives.extend(evaluated.iter().cloned());
+
+        let fronts = non_dominated_sort(&objectives);
+        let mut rank = vec![usize::MAX; objectives.len()];
+        let mut crowding = vec![0.0; objectives.len()];
+        let mut survivors = Vec::with_capacity(size);
+
+        for (front_rank, front) in fronts.iter().enumerate() {
+            let distances = crowding_distance(&objectives, front);
+            let mut front: Vec<_> = front.iter().copied().zip(distances).collect();
+
+            for &(index, distance) in &front {
+                rank[index] = front_rank;
+                crowding[index] = distance;
+            }
+
+            if survivors.len() + front.len() > size {
+                front.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+                front.truncate(size - survivors.len());
+            }
+            survivors.extend(front.into_iter().map(|(index, _)| index));
+
+            if survivors.len() == size {
+                break;
+            }
+        }
+
+        let tournament = |rng: &mut dyn RngCore| {
+            let a = survivors[rng.gen_range(0..survivors.len())];
+            let b = survivors[rng.gen_range(0..survivors.len())];
+
+            match rank[a].cmp(&rank[b]) {
+                Ordering::Less => a,
+                Ordering::Greater => b,
+                Ordering::Equal if crowding[b] > crowding[a] => b,
+                Ordering::Equal => a,
+            }
+        };
+
+        let new_population = (0..size)
+            .map(|_| {
+                let parent_one = tournament(rng);
+                let parent_two = tournament(rng);
+                let mut child = self.crossover_method.crossover(
+                    rng,
+                    &chromosomes[parent_one],
+                    &chromosomes[parent_two],
+                );
+
+                self.mutation_method.mutate(rng, &mut child);
+                if let Some(bounds) = &self.bounds {
+                    bounds.apply(rng, &mut child);
+                }
+                I::create(child)
+            })
+            .collect();
+
+        let mut statistics = ParetoStatistics::summarize(&evaluated);
+        statistics.front_sizes = fronts.iter().map(Vec::len).collect();
+        statistics.pareto_front = fronts[0]
+            .iter()
+            .map(|&index| objectives[index].clone())
+            .collect();
+
+        self.parents = survivors
+            .into_iter()
+            .map(|index| (chromosomes[index].clone(), objectives[index].clone()))
+            .collect();
+
+        (new_population, statistics)
+    }
+}
+
+/// What [`Nsga2::evolve`] reports instead of [`Statistics`](crate::Statistics).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParetoStatistics {
+    /// Size of each non-dominated front, best first, among the solutions
+    /// survivors were picked from.
+    pub front_sizes: Vec<usize>,
+    /// Objectives of every solution on the first front, i.e. the best
+    /// trade-offs found so far.
+    pub pareto_front: Vec<Vec<f32>>,
+    /// Per-objective minimum, maximum and mean over the evaluated
+    /// population alone.
+    pub min_objectives: Vec<f32>,
+    pub max_objectives: Vec<f32>,
+    pub avg_objectives: Vec<f32>,
+}
+
+impl ParetoStatistics {
+    /// Sorts a population on its own, without the survivors
+    /// [`Nsga2::evolve`] would compare it with.
+    pub fn analyze<I>(population: &[I]) -> Self
+    where
+        I: MultiObjectiveIndividual,
+    {
+        let objectives: Vec<_> = population
+            .iter()
+            .map(|individual| individual.objectives())
+            .collect();
+        let fronts = non_dominated_sort(&objectives);
+
+        let mut statistics = Self::summarize(&objectives);
+        statistics.front_sizes = fronts.iter().map(Vec::len).collect();
+        statistics.pareto_front = fronts[0]
+            .iter()
+            .map(|&index| objectives[index].clone())
+            .collect();
+        statistics
+    }
+    fn summarize(objectives: &[Vec<f32>]) -> Self {
+        assert!(!objectives.is_empty());
+
+        let count = objectives[0].len();
+        let column = |objective: usize| objectives.iter().map(move |values| values[objective]);
+
+        Self {
+            front_sizes: Vec::new(),
+            pareto_front: Vec::new(),
+            min_objectives: (0..count)
+                .map(|objective| column(objective).fold(f32::INFINITY, f32::min))
+                .collect(),
+            max_objectives: (0..count)
+                .map(|objective| column(objective).fold(f32::NEG_INFINITY, f32::max))
+                .collect(),
+            avg_objectives: (0..count)
+                .map(|objective| column(objective).sum::<f32>() / objectives.len() as f32)
+                .collect(),
+        }
+    }
+    pub fn front_sizes(&self) -> &[usize] {
+        &self.front_sizes
+    }
+    pub fn pareto_front(&self) -> &[Vec<f32>] {
+        &self.pareto_front
+    }
+    /// One row per solution on the first front, one column per objective,
+    /// without a header.
+    pub fn write_front_csv(&self, mut writer: impl io::Write) -> io::Result<()> {
+        for objectives in &self.pareto_front {
+            let row: Vec<_> = objectives.iter().map(f32::to_string).collect();
+            writeln!(writer, "{}", row.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether `a` is at least as good as `b` on every objective and better on
+/// at least one, with every objective maximized.
+pub fn dominates(a: &[f32], b: &[f32]) -> bool {
+    a.iter().zip(b).all(|(a, b)| a >= b) && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+/// Splits solutions into fronts of indices, best first: the first front
+/// holds the solutions nothing dominates, the second those only the first
+/// front dominates, and so on.
+pub fn non_dominated_sort(objectives: &[Vec<f32>]) -> Vec<Vec<usize>> {
+    let count = objectives.len();
+    let mut dominated = vec![Vec::new(); count];
+    let mut dominators = vec![0usize; count];
+
+    for a in 0..count {
+        for b in a + 1..count {
+            if dominates(&objectives[a], &objectives[b]) {
+                dominated[a].push(b);
+                dominators[b] += 1;
+            } else if dominates(&objectives[b], &objectives[a]) {
+                dominated[b].push(a);
+                dominators[a] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+    let mut front: Vec<usize> = (0..count).filter(|&i| dominators[i] == 0).collect();
+
+    while !front.is_empty() {
+        let mut next = Vec::new();
+        for &i in &front {
+            for &j in &dominated[i] {
+                dominators[j] -= 1;
+                if dominators[j] == 0 {
+                    next.push(j);
+                }
+            }
+        }
+        fronts.push(front);
+        front = next;
+    }
+    fronts
+}
+
+/// Crowding distance of each solution in `front`, in the same order: the
+/// sum over objectives of the normalized gap between its two neighbours.
+/// Solutions at either end of an objective's range get infinity, so they
+/// are always kept.
+pub fn crowding_distance(objectives: &[Vec<f32>], front: &[usize]) -> Vec<f32> {
+    let mut distances = vec![0.0; front.len()];
+    let Some(&first) = front.first() else {
+        return distances;
+    };
+    let mut order: Vec<usize> = (0..front.len()).collect();
+
+    let columns = (0..objectives[first].len()).map(|objective| {
+        front
+            .iter()
+            .map(|&i| objectives[i][objective])
+            .collect::<Vec<_>>()
+    });
+
+    for values in columns {
+        let value = |i: usize| values[i];
+
+        order.sort_by(|&a, &b| value(a).partial_cmp(&value(b)).unwrap_or(Ordering::Equal));
+
+        let (low, high) = (order[0], order[order.len() - 1]);
+        distances[low] = f32::INFINITY;
+        distances[high] = f32::INFINITY;
+
+        let range = value(high) - value(low);
+        if range <= 0.0 {
+            continue;
+        }
+        for window in order.windows(3) {
+            distances[window[1]] += (value(window[2]) - value(window[0])) / range;
+        }
+    }
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoundPolicy, GaussianMutation, UniformCrossOver};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Two conflicting objectives: the first gene, and minus the second.
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl MultiObjectiveIndividual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+        fn objectives(&self) -> Vec<f32> {
+            let genes: Vec<_> = self.chromosome.iter().copied().collect();
+            vec![genes[0], -genes[1]]
+        }
+    }
+
+    #[test]
+    fn sorts_into_fronts() {
+        let objectives = vec![
+            vec![1.0, 1.0],
+            vec![3.0, 1.0],
+            vec![2.0, 2.0],
+            vec![0.0, 3.0],
+            vec![1.0, 2.0],
+        ];
+
+        assert_eq!(
+            non_dominated_sort(&objectives),
+            vec![vec![1, 2, 3], vec![4], vec![0]]
+        );
+    }
+
+    #[test]
+    fn boundary_solutions_are_least_crowded() {
+        let objectives = vec![
+            vec![0.0, 4.0],
+            vec![1.0, 3.0],
+            vec![3.0, 1.0],
+            vec![4.0, 0.0],
+        ];
+
+        // Both inner solutions span 3 of 4 on each objective.
+        assert_eq!(
+            crowding_distance(&objectives, &[0, 1, 2, 3]),
+            vec![f32::INFINITY, 1.5, 1.5, f32::INFINITY]
+        );
+    }
+
+    #[test]
+    fn dominated_offspring_lose_to_surviving_parents() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut nsga2 = Nsga2::new(UniformCrossOver, GaussianMutation::new(0.0, 0.0));
+        let population = |genes: &[[f32; 2]]| -> Vec<TestIndividual> {
+            genes
+                .iter()
+                .map(|genes| TestIndividual::create(genes.iter().copied().collect()))
+                .collect()
+        };
+
+        nsga2.evolve(&mut rng, &population(&[[1.0, 0.0], [0.0, -1.0]]));
+        let (new_population, statistics) =
+            nsga2.evolve(&mut rng, &population(&[[0.0, 0.0], [-1.0, 1.0]]));
+
+        assert_eq!(statistics.front_sizes, vec![2, 1, 1]);
+        assert_eq!(
+            statistics.pareto_front,
+            vec![vec![1.0, 0.0], vec![0.0, 1.0]]
+        );
+        assert_eq!(statistics.min_objectives, vec![-1.0, -1.0]);
+        assert_eq!(statistics.max_objectives, vec![0.0, 0.0]);
+        // Only the surviving parents breed, and without mutation their
+        // children can't leave the box they span.
+        assert!(new_population.iter().all(|individual| {
+            individual
+                .chromosome
+                .iter()
+                .all(|gene| (-1.0..=1.0).contains(gene))
+                && individual.objectives()[0] >= 0.0
+        }));
+    }
+
+    #[test]
+    fn children_respect_bounds() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut nsga2 = Nsga2::new(UniformCrossOver, GaussianMutation::new(1.0, 100.0))
+            .with_bounds(Bounds::new(BoundPolicy::Clamp).with(2, -1.0..=1.0));
+        let population: Vec<_> = (0..10)
+            .map(|_| TestIndividual::create(vec![0.5; 2].into_iter().collect()))
+            .collect();
+
+        let (new_population, _) = nsga2.evolve(&mut rng, &population);
+
+        assert!(new_population.iter().all(|individual| individual
+            .chromosome
+            .iter()
+            .all(|gene| (-1.0..=1.0).contains(gene))));
+    }
+}
@@ -1,12 +1,150 @@
-pub use self::roulette::*;
+pub use self::{rank::*, roulette::*, sus::*, tournament::*, truncation::*};
 use crate::individual::*;
 
+mod rank;
 mod roulette;
+mod sus;
+mod tournament;
+mod truncation;
+
+use std::cmp::Ordering;
 
 use rand::RngCore;
 
 pub trait SelectionMethod {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual<G>;
+
+    /// Picks `count` parents at once. Defaults to `count` independent calls
+    /// to [`SelectionMethod::select`]; methods that spread their picks over
+    /// the whole population, like [`StochasticUniversalSampling`], override
+    /// it.
+    fn select_many<'a, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I>
     where
-        I: Individual;
+        I: Individual<G>,
+    {
+        (0..count).map(|_| self.select(rng, population)).collect()
+    }
+}
+
+/// Indices of `population`, fittest first.
+fn by_fitness<I, G>(population: &[I]) -> Vec<usize>
+where
+    I: Individual<G>,
+{
+    let mut indices: Vec<usize> = (0..population.len()).collect();
+    indices.sort_by(|&a, &b| {
+        population[b]
+            .fitness()
+            .partial_cmp(&population[a].fitness())
+            .unwrap_or(Ordering::Equal)
+    });
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Chromosome;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Single-gene individual whose fitness is its gene.
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().sum()
+        }
+    }
+
+    fn population(fitness: &[f32]) -> Vec<TestIndividual> {
+        fitness
+            .iter()
+            .map(|&fitness| TestIndividual::create([fitness].into_iter().collect()))
+            .collect()
+    }
+
+    /// How many times each individual gets picked in `rounds` batches of
+    /// `population.len()` picks.
+    fn histogram(
+        method: &impl SelectionMethod,
+        population: &[TestIndividual],
+        rounds: usize,
+    ) -> Vec<usize> {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut histogram = vec![0; population.len()];
+
+        for _ in 0..rounds {
+            for picked in method.select_many(&mut rng, population, population.len()) {
+                let index = population
+                    .iter()
+                    .position(|individual| std::ptr::eq(individual, picked))
+                    .unwrap();
+                histogram[index] += 1;
+            }
+        }
+        histogram
+    }
+
+    #[test]
+    fn roulette_falls_back_to_uniform_on_zero_fitness() {
+        let histogram = histogram(&RouletteWheel, &population(&[0.0; 4]), 1000);
+
+        assert!(histogram.iter().all(|&count| (850..1150).contains(&count)));
+    }
+
+    #[test]
+    fn sus_picks_every_individual_its_expected_number_of_times() {
+        let population = population(&[1.0, 2.0, 3.0, 4.0]);
+        let histogram = histogram(&StochasticUniversalSampling, &population, 100);
+
+        // Expected picks per batch are 0.4, 0.8, 1.2 and 1.6, and SUS never
+        // strays more than one away from them.
+        assert!(histogram[0] <= 100);
+        assert!((100..=200).contains(&histogram[2]));
+        assert!((100..=200).contains(&histogram[3]));
+    }
+
+    #[test]
+    fn rank_and_tournament_favour_the_fittest() {
+        let population = population(&[1.0, 1000.0, 2.0, 3.0]);
+
+        for histogram in [
+            histogram(&RankSelection, &population, 1000),
+            histogram(&TournamentSelection::new(3), &population, 1000),
+        ] {
+            assert_eq!(
+                histogram.iter().max(),
+                Some(&histogram[1]),
+                "{:?}",
+                histogram
+            );
+            // Unlike roulette, the outlier doesn't take (almost) everything.
+            assert!(histogram[3] > 100, "{:?}", histogram);
+        }
+    }
+
+    #[test]
+    fn truncation_only_picks_the_top() {
+        let population = population(&[4.0, 1.0, 3.0, 2.0]);
+        let histogram = histogram(&TruncationSelection::new(0.5), &population, 100);
+
+        assert_eq!(histogram[1] + histogram[3], 0);
+        assert_eq!(histogram[0] + histogram[2], 400);
+    }
 }
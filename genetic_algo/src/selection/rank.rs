@@ -0,0 +1,40 @@
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::RngCore;
+
+use crate::individual::*;
+
+use crate::*;
+
+/// Linear ranking: the worst individual gets weight 1, the best weight
+/// `population.len()`, however far apart their fitness is.
+#[derive(Clone, Debug, Default)]
+pub struct RankSelection;
+
+impl SelectionMethod for RankSelection {
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual<G>,
+    {
+        self.select_many(rng, population, 1)[0]
+    }
+
+    /// Ranks the population and builds the weights once for all picks.
+    fn select_many<'a, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I>
+    where
+        I: Individual<G>,
+    {
+        let ranked = super::by_fitness(population);
+        let length = ranked.len();
+        let weights = WeightedIndex::new((0..length).map(|position| length - position))
+            .expect("Empty population!");
+
+        (0..count)
+            .map(|_| &population[ranked[weights.sample(rng)]])
+            .collect()
+    }
+}
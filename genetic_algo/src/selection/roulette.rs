@@ -1,19 +1,26 @@
+use rand::distributions::WeightedError;
 use rand::prelude::SliceRandom;
 
 use crate::individual::*;
 
 use crate::*;
 
+/// Fitness-proportionate selection. When every individual has zero fitness
+/// it picks uniformly instead.
 #[derive(Clone, Debug, Default)]
 pub struct RouletteWheel;
 
 impl SelectionMethod for RouletteWheel {
-    fn select<'a, I>(&self, rng: &mut dyn rand::RngCore, population: &'a [I]) -> &'a I
+    fn select<'a, I, G>(&self, rng: &mut dyn rand::RngCore, population: &'a [I]) -> &'a I
     where
-        I: Individual,
+        I: Individual<G>,
     {
-        population
-            .choose_weighted(rng, |individual| individual.fitness())
-            .expect("Empty population!")
+        match population.choose_weighted(rng, |individual| individual.fitness()) {
+            Ok(individual) => individual,
+            Err(WeightedError::AllWeightsZero) => {
+                population.choose(rng).expect("Empty population!")
+            }
+            Err(err) => panic!("Can't spin the roulette wheel: {}", err),
+        }
     }
 }
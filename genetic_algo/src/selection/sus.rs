@@ -0,0 +1,71 @@
+use rand::prelude::SliceRandom;
+use rand::{Rng, RngCore};
+
+use crate::individual::*;
+
+use crate::*;
+
+/// Stochastic universal sampling: one spin of a roulette wheel with
+/// `count` evenly spaced pointers, so every individual is picked within one
+/// of its expected number of times.
+///
+/// Falls back to uniform selection when every individual has zero
+/// fitness, like [`RouletteWheel`].
+#[derive(Clone, Debug, Default)]
+pub struct StochasticUniversalSampling;
+
+impl SelectionMethod for StochasticUniversalSampling {
+    /// A single pointer, i.e. a roulette wheel spin.
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual<G>,
+    {
+        RouletteWheel.select(rng, population)
+    }
+
+    fn select_many<'a, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I>
+    where
+        I: Individual<G>,
+    {
+        assert!(!population.is_empty(), "Empty population!");
+
+        let total: f32 = population
+            .iter()
+            .map(|individual| individual.fitness().max(0.0))
+            .sum();
+
+        if total <= 0.0 || count == 0 {
+            return (0..count)
+                .map(|_| population.choose(rng).expect("Empty population!"))
+                .collect();
+        }
+
+        let spacing = total / count as f32;
+        let mut pointer = rng.gen_range(0.0..spacing);
+        let mut cumulative = 0.0;
+        let mut selected = Vec::with_capacity(count);
+
+        for individual in population {
+            cumulative += individual.fitness().max(0.0);
+
+            while pointer < cumulative && selected.len() < count {
+                selected.push(individual);
+                pointer += spacing;
+            }
+        }
+        // Rounding can leave the last pointer just past the end.
+        while selected.len() < count {
+            selected.push(&population[population.len() - 1]);
+        }
+
+        // Picks come out in population order; shuffle them so consecutive
+        // parents aren't neighbours.
+        selected.shuffle(rng);
+        selected
+    }
+}
@@ -0,0 +1,38 @@
+use rand::prelude::SliceRandom;
+use rand::RngCore;
+
+use crate::individual::*;
+
+use crate::*;
+
+/// Picks `size` individuals at random, with replacement, and keeps the
+/// fittest. Larger tournaments mean stronger selection pressure.
+#[derive(Clone, Debug)]
+pub struct TournamentSelection {
+    pub size: usize,
+}
+
+impl TournamentSelection {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0);
+        Self { size }
+    }
+}
+
+impl SelectionMethod for TournamentSelection {
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual<G>,
+    {
+        (0..self.size)
+            .map(|_| population.choose(rng).expect("Empty population!"))
+            .reduce(|best, contender| {
+                if contender.fitness() > best.fitness() {
+                    contender
+                } else {
+                    best
+                }
+            })
+            .expect("tournaments have at least one contender")
+    }
+}
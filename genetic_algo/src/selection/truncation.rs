@@ -0,0 +1,47 @@
+use rand::prelude::SliceRandom;
+use rand::RngCore;
+
+use crate::individual::*;
+
+use crate::*;
+
+/// Picks uniformly among the fittest `fraction` of the population (at
+/// least one individual).
+#[derive(Clone, Debug)]
+pub struct TruncationSelection {
+    pub fraction: f32,
+}
+
+impl TruncationSelection {
+    pub fn new(fraction: f32) -> Self {
+        assert!(fraction > 0.0 && fraction <= 1.0);
+        Self { fraction }
+    }
+}
+
+impl SelectionMethod for TruncationSelection {
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual<G>,
+    {
+        self.select_many(rng, population, 1)[0]
+    }
+
+    /// Ranks the population once for all picks.
+    fn select_many<'a, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I>
+    where
+        I: Individual<G>,
+    {
+        let ranked = super::by_fitness(population);
+        let kept = ((ranked.len() as f32 * self.fraction).ceil() as usize).clamp(1, ranked.len());
+
+        (0..count)
+            .map(|_| &population[*ranked[..kept].choose(rng).expect("Empty population!")])
+            .collect()
+    }
+}
@@ -0,0 +1,209 @@
+use std::cmp::Ordering;
+
+use rand::{seq::SliceRandom, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::chromosome::Chromosome;
+use crate::gene::Gene;
+use crate::individual::Individual;
+
+/// Optional speciation for [`GeneticAlgorithm`](crate::GeneticAlgorithm):
+/// individuals are clustered by chromosome distance, fitness is shared
+/// within each species and every species breeds its own share of the next
+/// generation, so one strategy can't take over the whole population at
+/// once.
+///
+/// Species persist between generations; see [`Neat`](crate::neat::Neat)
+/// for the topology-evolving counterpart.
+#[derive(Clone, Debug)]
+pub struct Speciation {
+    /// Individuals within this root-mean-square gene distance of a
+    /// species' representative join it.
+    pub threshold: f32,
+    species: Vec<Cluster>,
+    next_id: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Cluster {
+    id: usize,
+    age: usize,
+    best_fitness: f32,
+    /// Genes of a member, as real numbers.
+    representative: Chromosome,
+    /// Indices into the population currently being evolved.
+    members: Vec<usize>,
+}
+
+/// A species as of the generation it was reported with.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesStatistics {
+    pub id: usize,
+    pub size: usize,
+    /// Generations this species has existed for.
+    pub age: usize,
+    /// Best raw fitness any member ever reached.
+    pub best_fitness: f32,
+}
+
+impl Speciation {
+    pub fn new(threshold: f32) -> Self {
+        assert!(threshold >= 0.0);
+        Self {
+            threshold,
+            species: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Assigns every individual to the first compatible species, founding
+    /// new ones as needed, and picks each species' next representative.
+    pub(crate) fn speciate<I, G>(&mut self, rng: &mut dyn RngCore, population: &[I])
+    where
+        I: Individual<G>,
+        G: Gene,
+    {
+        for species in &mut self.species {
+            species.members.clear();
+        }
+
+        for (index, individual) in population.iter().enumerate() {
+            let chromosome = individual.chromosome();
+            let threshold = self.threshold;
+
+            match self
+                .species
+                .iter_mut()
+                .find(|species| distance(&species.representative, chromosome) <= threshold)
+            {
+                Some(species) => species.members.push(index),
+                None => {
+                    self.species.push(Cluster {
+                        id: self.next_id,
+                        age: 0,
+                        best_fitness: f32::MIN,
+                        representative: chromosome.to_reals(),
+                        members: vec![index],
+                    });
+                    self.next_id += 1;
+                }
+            }
+        }
+
+        self.species.retain(|species| !species.members.is_empty());
+
+        for species in &mut self.species {
+            species.age += 1;
+            species.best_fitness = species
+                .members
+                .iter()
+                .map(|&index| population[index].fitness())
+                .fold(species.best_fitness, f32::max);
+
+            let representative = *species
+                .members
+                .choose(rng)
+                .expect("empty species were removed");
+            species.representative = population[representative].chromosome().to_reals();
+        }
+    }
+
+    /// Members of every species, in the order of [`Speciation::offspring`].
+    pub(crate) fn members(&self) -> Vec<Vec<usize>> {
+        self.species
+            .iter()
+            .map(|species| species.members.clone())
+            .collect()
+    }
+
+    /// Splits `total` children between the species by explicit fitness
+    /// sharing: each member's fitness is divided by its species' size.
+    pub(crate) fn offspring<I, G>(&self, population: &[I], total: usize) -> Vec<usize>
+    where
+        I: Individual<G>,
+    {
+        let shares: Vec<f32> = self
+            .species
+            .iter()
+            .map(|species| {
+                species
+                    .members
+                    .iter()
+                    .map(|&index| population[index].fitness().max(0.0))
+                    .sum::<f32>()
+                    / species.members.len() as f32
+            })
+            .collect();
+        let sizes: Vec<usize> = self
+            .species
+            .iter()
+            .map(|species| species.members.len())
+            .collect();
+
+        allocate_offspring(&shares, &sizes, total)
+    }
+
+    pub fn statistics(&self) -> Vec<SpeciesStatistics> {
+        self.species
+            .iter()
+            .map(|species| SpeciesStatistics {
+                id: species.id,
+                size: species.members.len(),
+                age: species.age,
+                best_fitness: species.best_fitness,
+            })
+            .collect()
+    }
+}
+
+/// Root-mean-square difference between two chromosomes' genes, as real
+/// numbers.
+fn distance<G>(a: &Chromosome, b: &Chromosome<G>) -> f32
+where
+    G: Gene,
+{
+    if a.len() != b.len() {
+        return f32::INFINITY;
+    }
+    if a.is_empty() {
+        return 0.0;
+    }
+
+    (a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| (a - b.to_f32()).powi(2))
+        .sum::<f32>()
+        / a.len() as f32)
+        .sqrt()
+}
+
+/// Splits `total` children in proportion to `shares`, or to `sizes` when
+/// nobody has scored yet. Rounding leftovers go to the largest remainders.
+pub(crate) fn allocate_offspring(shares: &[f32], sizes: &[usize], total: usize) -> Vec<usize> {
+    let shares: Vec<f32> = if shares.iter().sum::<f32>() > 0.0 {
+        shares.to_vec()
+    } else {
+        sizes.iter().map(|&size| size as f32).collect()
+    };
+
+    let sum: f32 = shares.iter().sum();
+    let exact: Vec<f32> = shares
+        .iter()
+        .map(|share| share / sum * total as f32)
+        .collect();
+    let mut counts: Vec<usize> = exact.iter().map(|count| count.floor() as usize).collect();
+
+    let mut by_remainder: Vec<usize> = (0..exact.len()).collect();
+    by_remainder.sort_by(|&a, &b| {
+        exact[b]
+            .fract()
+            .partial_cmp(&exact[a].fract())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let remaining = total.saturating_sub(counts.iter().sum());
+    for &index in by_remainder.iter().cycle().take(remaining) {
+        counts[index] += 1;
+    }
+    counts
+}
@@ -1,39 +1,106 @@
 use std::cmp::Ordering;
+use std::io;
 
+use serde::{Deserialize, Serialize};
+
+use crate::chromosome::Chromosome;
+use crate::gene::Gene;
 use crate::individual::Individual;
+use crate::speciation::SpeciesStatistics;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Statistics {
     pub min_fitness: f32,
     pub max_fitness: f32,
     pub avg_fitness: f32,
+    pub median_fitness: f32,
+    /// Population standard deviation of the fitness.
+    pub std_fitness: f32,
+    pub p10_fitness: f32,
+    pub p25_fitness: f32,
+    pub p75_fitness: f32,
+    pub p90_fitness: f32,
+    /// Index of the fittest individual in the analyzed population.
+    pub best_index: usize,
+    /// Its chromosome, with genes as real numbers; `None` for populations
+    /// without chromosomes, and never exported.
+    #[serde(skip)]
+    pub best_chromosome: Option<Chromosome>,
+    /// Mean Euclidean distance of the chromosomes to their centroid; zero
+    /// when unknown.
+    pub diversity: f32,
+    /// Indices, in the analyzed population, of the individuals copied
+    /// unchanged into the next generation by elitism.
+    #[serde(default)]
+    pub elite_indices: Vec<usize>,
+    /// Species found this generation, if the algorithm speciates.
+    #[serde(default)]
+    pub species: Vec<SpeciesStatistics>,
 }
 
 impl Statistics {
-    pub fn analyze<I>(population: &[I]) -> Self
+    pub fn analyze<I, G>(population: &[I]) -> Self
     where
-        I: Individual,
+        I: Individual<G>,
+        G: Gene,
     {
-        assert!(!population.is_empty());
+        let mut statistics =
+            Self::from_fitness(population.iter().map(|individual| individual.fitness()));
+
+        statistics.best_chromosome =
+            Some(population[statistics.best_index].chromosome().to_reals());
+        statistics.diversity =
+            diversity(population.iter().map(|individual| individual.chromosome()));
+        statistics
+    }
+    /// Same as [`Statistics::analyze`] for populations that aren't made of
+    /// [`Individual`]s, e.g. NEAT genomes; leaves the chromosome-based
+    /// fields empty.
+    pub fn from_fitness(fitness: impl IntoIterator<Item = f32>) -> Self {
+        let fitness: Vec<_> = fitness.into_iter().collect();
+        assert!(!fitness.is_empty());
+
+        let best_index = (0..fitness.len())
+            .max_by(|&a, &b| {
+                fitness[a]
+                    .partial_cmp(&fitness[b])
+                    .unwrap_or(Ordering::Equal)
+            })
+            .expect("fitness isn't empty");
 
-        let length = population.len();
         let fitness_data = {
-            let mut fitnesses: Vec<_> = population
-                .iter()
-                .map(|individual| individual.fitness())
-                .collect();
+            let mut fitnesses = fitness;
             fitnesses.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
             fitnesses
         };
 
+        let length = fitness_data.len();
+
         let min_fitness = fitness_data[0];
         let max_fitness = fitness_data[length - 1];
         let avg_fitness = fitness_data.iter().sum::<f32>() / length as f32;
+        let std_fitness = (fitness_data
+            .iter()
+            .map(|fitness| (fitness - avg_fitness).powi(2))
+            .sum::<f32>()
+            / length as f32)
+            .sqrt();
 
         Self {
             min_fitness,
             max_fitness,
             avg_fitness,
+            median_fitness: percentile(&fitness_data, 50.0),
+            std_fitness,
+            p10_fitness: percentile(&fitness_data, 10.0),
+            p25_fitness: percentile(&fitness_data, 25.0),
+            p75_fitness: percentile(&fitness_data, 75.0),
+            p90_fitness: percentile(&fitness_data, 90.0),
+            best_index,
+            best_chromosome: None,
+            diversity: 0.0,
+            elite_indices: Vec::new(),
+            species: Vec::new(),
         }
     }
     pub fn min_fitness(&self) -> f32 {
@@ -45,4 +112,199 @@ impl Statistics {
     pub fn avg_fitness(&self) -> f32 {
         self.avg_fitness
     }
+    pub fn median_fitness(&self) -> f32 {
+        self.median_fitness
+    }
+    pub fn std_fitness(&self) -> f32 {
+        self.std_fitness
+    }
+    pub fn best_index(&self) -> usize {
+        self.best_index
+    }
+    pub fn best_chromosome(&self) -> Option<&Chromosome> {
+        self.best_chromosome.as_ref()
+    }
+    pub fn diversity(&self) -> f32 {
+        self.diversity
+    }
+    pub fn elite_indices(&self) -> &[usize] {
+        &self.elite_indices
+    }
+    pub fn species(&self) -> &[SpeciesStatistics] {
+        &self.species
+    }
+}
+
+/// Linearly interpolated percentile `p` (0 to 100) of sorted, non-empty
+/// `data`.
+fn percentile(data: &[f32], p: f32) -> f32 {
+    let rank = p / 100.0 * (data.len() - 1) as f32;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+
+    data[low] + (data[high] - data[low]) * (rank - low as f32)
+}
+
+/// Mean distance of `chromosomes` to their centroid, or zero if their
+/// lengths differ.
+fn diversity<'a, G>(chromosomes: impl Iterator<Item = &'a Chromosome<G>> + Clone) -> f32
+where
+    G: Gene + 'a,
+{
+    let count = chromosomes.clone().count();
+    let Some(length) = chromosomes.clone().next().map(Chromosome::len) else {
+        return 0.0;
+    };
+    if chromosomes
+        .clone()
+        .any(|chromosome| chromosome.len() != length)
+    {
+        return 0.0;
+    }
+
+    let mut centroid = vec![0.0; length];
+    for chromosome in chromosomes.clone() {
+        for (sum, gene) in centroid.iter_mut().zip(chromosome.iter()) {
+            *sum += gene.to_f32() / count as f32;
+        }
+    }
+
+    chromosomes
+        .map(|chromosome| {
+            chromosome
+                .iter()
+                .zip(&centroid)
+                .map(|(gene, center)| (gene.to_f32() - center).powi(2))
+                .sum::<f32>()
+                .sqrt()
+        })
+        .sum::<f32>()
+        / count as f32
+}
+
+/// Per-generation [`Statistics`] of a run, exportable as CSV or JSON.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatisticsHistory {
+    generations: Vec<Statistics>,
+}
+
+impl StatisticsHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn push(&mut self, statistics: Statistics) {
+        self.generations.push(statistics);
+    }
+    pub fn generations(&self) -> &[Statistics] {
+        &self.generations
+    }
+    pub fn len(&self) -> usize {
+        self.generations.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.generations.is_empty()
+    }
+    /// One row per generation, with a header. Chromosomes and elite
+    /// indices are left out.
+    pub fn write_csv(&self, mut writer: impl io::Write) -> io::Result<()> {
+        writeln!(
+            writer,
+            "generation,min_fitness,max_fitness,avg_fitness,median_fitness,std_fitness,\
+             p10_fitness,p25_fitness,p75_fitness,p90_fitness,best_index,diversity"
+        )?;
+
+        for (generation, statistics) in self.generations.iter().enumerate() {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{},{},{},{}",
+                generation,
+                statistics.min_fitness,
+                statistics.max_fitness,
+                statistics.avg_fitness,
+                statistics.median_fitness,
+                statistics.std_fitness,
+                statistics.p10_fitness,
+                statistics.p25_fitness,
+                statistics.p75_fitness,
+                statistics.p90_fitness,
+                statistics.best_index,
+                statistics.diversity,
+            )?;
+        }
+        Ok(())
+    }
+    /// A JSON array with one object per generation.
+    pub fn write_json(&self, writer: impl io::Write) -> serde_json::Result<()> {
+        serde_json::to_writer_pretty(writer, &self.generations)
+    }
+}
+
+impl Extend<Statistics> for StatisticsHistory {
+    fn extend<T: IntoIterator<Item = Statistics>>(&mut self, iter: T) {
+        self.generations.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().next().copied().unwrap_or_default()
+        }
+    }
+
+    #[test]
+    fn analyze_reports_spread_best_and_diversity() {
+        let population: Vec<_> = [[4.0, 0.0], [1.0, 0.0], [3.0, 0.0], [2.0, 0.0], [5.0, 0.0]]
+            .into_iter()
+            .map(|genes| TestIndividual::create(genes.into_iter().collect()))
+            .collect();
+
+        let statistics = Statistics::analyze(&population);
+
+        assert_eq!(statistics.median_fitness, 3.0);
+        assert_eq!(statistics.p25_fitness, 2.0);
+        assert_eq!(statistics.p90_fitness, 4.6);
+        assert!((statistics.std_fitness - 2.0f32.sqrt()).abs() < 1e-6);
+        assert_eq!(statistics.best_index, 4);
+        assert_eq!(
+            statistics.best_chromosome,
+            Some([5.0, 0.0].into_iter().collect())
+        );
+        // Distances to the centroid (3, 0) are 1, 2, 0, 1 and 2.
+        assert!((statistics.diversity - 1.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn history_exports_csv_and_json() {
+        let mut history = StatisticsHistory::new();
+        history.push(Statistics::from_fitness([1.0, 3.0]));
+        history.push(Statistics::from_fitness([2.0]));
+
+        let mut csv = Vec::new();
+        history.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("generation,min_fitness,max_fitness"));
+        assert_eq!(lines[1], "0,1,3,2,2,1,1.2,1.5,2.5,2.8,1,0");
+
+        let mut json = Vec::new();
+        history.write_json(&mut json).unwrap();
+        let parsed: Vec<Statistics> = serde_json::from_slice(&json).unwrap();
+
+        assert_eq!(parsed, history.generations());
+    }
 }
@@ -0,0 +1,137 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use lib_neuralnetwork::{Activation, LayerKind, Network, NetworkTopology, Scratch};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// The per-neuron layout `Network` had before its weights were made
+/// contiguous, kept as a baseline: every neuron owns its own `Vec` of
+/// weights, and every layer allocates its outputs.
+mod baseline {
+    use rand::{Rng, RngCore};
+
+    pub struct Network {
+        layers: Vec<Vec<Neuron>>,
+    }
+
+    struct Neuron {
+        weights: Vec<f32>,
+        bias: f32,
+    }
+
+    impl Network {
+        pub fn randomize(rng: &mut dyn RngCore, neurons: &[usize]) -> Self {
+            let layers = neurons
+                .windows(2)
+                .map(|sizes| {
+                    (0..sizes[1])
+                        .map(|_| Neuron {
+                            weights: (0..sizes[0]).map(|_| rng.gen_range(-1.0..=1.0)).collect(),
+                            bias: rng.gen_range(-1.0..=1.0),
+                        })
+                        .collect()
+                })
+                .collect();
+
+            Self { layers }
+        }
+
+        pub fn propagate(&self, mut inputs: Vec<f32>) -> Vec<f32> {
+            for layer in &self.layers {
+                inputs = layer
+                    .iter()
+                    .map(|neuron| {
+                        let output = inputs
+                            .iter()
+                            .zip(&neuron.weights)
+                            .map(|(input, weight)| input * weight)
+                            .sum::<f32>();
+
+                        (neuron.bias + output).max(0.0)
+                    })
+                    .collect();
+            }
+            inputs
+        }
+    }
+}
+
+/// Same shape as the simulation's `Brain::topology` with the default config.
+fn topology() -> [NetworkTopology; 3] {
+    [
+        NetworkTopology {
+            neurons: 18,
+            activation: Activation::Identity,
+            kind: LayerKind::Dense,
+        },
+        NetworkTopology {
+            neurons: 36,
+            activation: Activation::Relu,
+            kind: LayerKind::Dense,
+        },
+        NetworkTopology {
+            neurons: 2,
+            activation: Activation::Tanh,
+            kind: LayerKind::Dense,
+        },
+    ]
+}
+
+fn propagate(c: &mut Criterion) {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut group = c.benchmark_group("population tick");
+
+    for population in [1000, 5000] {
+        let networks: Vec<_> = (0..population)
+            .map(|_| Network::randomize(&mut rng, &topology()))
+            .collect();
+        let baselines: Vec<_> = (0..population)
+            .map(|_| baseline::Network::randomize(&mut rng, &[18, 36, 2]))
+            .collect();
+        let inputs: Vec<Vec<f32>> = (0..population)
+            .map(|_| (0..18).map(|_| rng.gen()).collect())
+            .collect();
+
+        group.bench_with_input(
+            BenchmarkId::new("per_neuron_baseline", population),
+            &population,
+            |b, _| {
+                b.iter(|| {
+                    for (network, input) in baselines.iter().zip(&inputs) {
+                        black_box(network.propagate(input.clone()));
+                    }
+                })
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("propagate", population),
+            &population,
+            |b, _| {
+                b.iter(|| {
+                    for (network, input) in networks.iter().zip(&inputs) {
+                        black_box(network.propagate(input.clone()));
+                    }
+                })
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("propagate_into", population),
+            &population,
+            |b, _| {
+                let mut scratch = Scratch::default();
+
+                b.iter(|| {
+                    for (network, input) in networks.iter().zip(&inputs) {
+                        black_box(network.propagate_into(input, &mut scratch));
+                    }
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, propagate);
+criterion_main!(benches);
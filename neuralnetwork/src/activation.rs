@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// Non-linearity applied to every neuron of a layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Activation {
+    #[default]
+    Relu,
+    /// ReLU with a slope of 0.01 for negative inputs.
+    LeakyRelu,
+    Sigmoid,
+    Tanh,
+    Identity,
+    Softsign,
+}
+
+impl Activation {
+    pub fn apply(self, x: f32) -> f32 {
+        match self {
+            Self::Relu => x.max(0.0),
+            Self::LeakyRelu => {
+                if x > 0.0 {
+                    x
+                } else {
+                    0.01 * x
+                }
+            }
+            Self::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+            Self::Tanh => x.tanh(),
+            Self::Identity => x,
+            Self::Softsign => x / (1.0 + x.abs()),
+        }
+    }
+}
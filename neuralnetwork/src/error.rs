@@ -0,0 +1,58 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError {
+    /// A network needs at least an input and an output layer.
+    TooFewLayers(usize),
+    WeightCount {
+        expected: usize,
+        found: usize,
+    },
+    InputSize {
+        expected: usize,
+        found: usize,
+    },
+    /// The [`NetworkState`](crate::NetworkState) was created for a network
+    /// with different recurrent layers.
+    StateMismatch,
+    /// A graph edge references a node that was never declared.
+    UnknownNode(usize),
+    /// A graph edge points into an input node.
+    InvalidEdge {
+        from: usize,
+        to: usize,
+    },
+    /// The graph's edges form a cycle.
+    Cycle,
+    /// A network used as a template has a different topology.
+    TopologyMismatch,
+    /// The noise of an [`Initializer::Perturb`](crate::Initializer::Perturb)
+    /// is negative or not finite.
+    InvalidNoise,
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewLayers(found) => {
+                write!(f, "expected at least 2 layers, got {}", found)
+            }
+            Self::WeightCount { expected, found } => {
+                write!(f, "expected {} weights, got {}", expected, found)
+            }
+            Self::InputSize { expected, found } => {
+                write!(f, "expected {} inputs, got {}", expected, found)
+            }
+            Self::StateMismatch => write!(f, "state doesn't match the network's layers"),
+            Self::UnknownNode(id) => write!(f, "edge references unknown node {}", id),
+            Self::InvalidEdge { from, to } => {
+                write!(f, "edge {} -> {} points into an input", from, to)
+            }
+            Self::Cycle => write!(f, "graph contains a cycle"),
+            Self::TopologyMismatch => write!(f, "network has a different topology"),
+            Self::InvalidNoise => write!(f, "noise must be finite and >= 0"),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use crate::{Activation, NetworkError, Scratch};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraphNode {
+    pub id: usize,
+    pub bias: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraphEdge {
+    pub from: usize,
+    pub to: usize,
+    pub weight: f32,
+}
+
+/// Network over an arbitrary acyclic graph of neurons, such as one evolved
+/// by NEAT.
+///
+/// Node ids `0..inputs` are the inputs, `inputs..inputs + outputs` the
+/// outputs and any other id a hidden neuron.
+#[derive(Debug, Clone)]
+pub struct GraphNetwork {
+    inputs: usize,
+    /// Every non-input neuron, in an order where each one comes after all
+    /// of its sources.
+    neurons: Vec<GraphNeuron>,
+    /// Slot in the value buffer of each output, in output order.
+    output_slots: Vec<usize>,
+    /// Size of the value buffer: one slot per input and per neuron.
+    slots: usize,
+}
+
+#[derive(Debug, Clone)]
+struct GraphNeuron {
+    slot: usize,
+    bias: f32,
+    activation: Activation,
+    /// Source slot and weight of every incoming edge.
+    incoming: Vec<(usize, f32)>,
+}
+
+impl GraphNetwork {
+    /// `nodes` supplies the biases of non-input neurons; outputs missing
+    /// from it get a bias of zero. Edges must reference known nodes, must not
+    /// point into an input and must not form a cycle.
+    pub fn try_new(
+        inputs: usize,
+        outputs: usize,
+        nodes: &[GraphNode],
+        edges: &[GraphEdge],
+        hidden_activation: Activation,
+        output_activation: Activation,
+    ) -> Result<Self, NetworkError> {
+        let is_output = |id: usize| (inputs..inputs + outputs).contains(&id);
+
+        // Slots: inputs first, then outputs, then hidden neurons.
+        let mut slot_of: HashMap<usize, usize> = (0..inputs + outputs).map(|id| (id, id)).collect();
+        let mut biases = vec![0.0; inputs + outputs];
+
+        for node in nodes.iter().filter(|node| node.id >= inputs) {
+            let slot = match slot_of.get(&node.id) {
+                Some(&slot) => slot,
+                None => {
+                    slot_of.insert(node.id, biases.len());
+                    biases.push(0.0);
+                    biases.len() - 1
+                }
+            };
+            biases[slot] = node.bias;
+        }
+
+        let slots = biases.len();
+        let mut incoming = vec![Vec::new(); slots];
+
+        let slot = |id: usize| {
+            slot_of
+                .get(&id)
+                .copied()
+                .ok_or(NetworkError::UnknownNode(id))
+        };
+
+        for edge in edges {
+            let (from, to) = (slot(edge.from)?, slot(edge.to)?);
+
+            if to < inputs {
+                return Err(NetworkError::InvalidEdge {
+                    from: edge.from,
+                    to: edge.to,
+                });
+            }
+            incoming[to].push((from, edge.weight));
+        }
+
+        // Kahn's algorithm over the non-input slots.
+        let mut pending: Vec<usize> = incoming
+            .iter()
+            .map(|edges| edges.iter().filter(|&&(from, _)| from >= inputs).count())
+            .collect();
+        let mut outgoing = vec![Vec::new(); slots];
+        for (to, edges) in incoming.iter().enumerate() {
+            for &(from, _) in edges {
+                outgoing[from].push(to);
+            }
+        }
+
+        let mut ready: Vec<usize> = (inputs..slots).filter(|&slot| pending[slot] == 0).collect();
+        let mut order = Vec::with_capacity(slots - inputs);
+
+        while let Some(slot) = ready.pop() {
+            order.push(slot);
+            for &to in &outgoing[slot] {
+                pending[to] -= 1;
+                if pending[to] == 0 {
+                    ready.push(to);
+                }
+            }
+        }
+
+        if order.len() != slots - inputs {
+            return Err(NetworkError::Cycle);
+        }
+
+        let id_of: HashMap<usize, usize> = slot_of.iter().map(|(&id, &slot)| (slot, id)).collect();
+        let neurons = order
+            .into_iter()
+            .map(|slot| GraphNeuron {
+                slot,
+                bias: biases[slot],
+                activation: if is_output(id_of[&slot]) {
+                    output_activation
+                } else {
+                    hidden_activation
+                },
+                incoming: std::mem::take(&mut incoming[slot]),
+            })
+            .collect();
+
+        Ok(Self {
+            inputs,
+            neurons,
+            output_slots: (inputs..inputs + outputs).collect(),
+            slots,
+        })
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+    pub fn outputs(&self) -> usize {
+        self.output_slots.len()
+    }
+
+    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
+        self.propagate_into(&inputs, &mut Scratch::default())
+            .to_vec()
+    }
+    /// Evaluates every neuron once, in dependency order, reusing `scratch`.
+    pub fn propagate_into<'a>(&self, input: &[f32], scratch: &'a mut Scratch) -> &'a [f32] {
+        self.try_propagate_into(input, scratch)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+    pub fn try_propagate_into<'a>(
+        &self,
+        input: &[f32],
+        scratch: &'a mut Scratch,
+    ) -> Result<&'a [f32], NetworkError> {
+        if input.len() != self.inputs {
+            return Err(NetworkError::InputSize {
+                expected: self.inputs,
+                found: input.len(),
+            });
+        }
+
+        let Scratch { front, back } = scratch;
+
+        front.clear();
+        front.extend_from_slice(input);
+        front.resize(self.slots, 0.0);
+
+        for neuron in &self.neurons {
+            let sum = neuron
+                .incoming
+                .iter()
+                .map(|&(from, weight)| front[from] * weight)
+                .sum::<f32>();
+
+            front[neuron.slot] = neuron.activation.apply(neuron.bias + sum);
+        }
+
+        back.clear();
+        back.extend(self.output_slots.iter().map(|&slot| front[slot]));
+
+        Ok(back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_hidden_nodes_before_outputs() {
+        // in(0) -> hidden(5) -> out(1), plus a direct in(0) -> out(1) edge.
+        let network = GraphNetwork::try_new(
+            1,
+            1,
+            &[
+                GraphNode { id: 5, bias: 1.0 },
+                GraphNode { id: 1, bias: 0.0 },
+            ],
+            &[
+                GraphEdge {
+                    from: 5,
+                    to: 1,
+                    weight: 2.0,
+                },
+                GraphEdge {
+                    from: 0,
+                    to: 5,
+                    weight: 1.0,
+                },
+                GraphEdge {
+                    from: 0,
+                    to: 1,
+                    weight: 1.0,
+                },
+            ],
+            Activation::Identity,
+            Activation::Identity,
+        )
+        .unwrap();
+
+        assert_eq!(network.propagate(vec![3.0]), vec![3.0 + 2.0 * (3.0 + 1.0)]);
+    }
+
+    #[test]
+    fn rejects_cycles() {
+        let edges = [
+            GraphEdge {
+                from: 2,
+                to: 3,
+                weight: 1.0,
+            },
+            GraphEdge {
+                from: 3,
+                to: 2,
+                weight: 1.0,
+            },
+        ];
+        let nodes = [
+            GraphNode { id: 2, bias: 0.0 },
+            GraphNode { id: 3, bias: 0.0 },
+        ];
+
+        assert_eq!(
+            GraphNetwork::try_new(1, 1, &nodes, &edges, Activation::Relu, Activation::Tanh)
+                .unwrap_err(),
+            NetworkError::Cycle
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use crate::Network;
+
+/// How [`Network::randomize_with`] picks a new network's weights.
+///
+/// Fan-in counts a neuron's incoming weights, recurrent ones included.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Initializer<'a> {
+    /// Biases and weights drawn uniformly from [-1, 1].
+    #[default]
+    Uniform,
+    /// Glorot uniform: weights from ±sqrt(6 / (fan_in + fan_out)), zero
+    /// biases. Suits tanh and sigmoid layers.
+    Xavier,
+    /// He uniform: weights from ±sqrt(6 / fan_in), zero biases. Suits ReLU
+    /// layers.
+    He,
+    Zeros,
+    /// A copy of `network`, which must have the requested topology, with
+    /// every weight and bias moved by up to `noise` (finite and >= 0) in
+    /// either direction.
+    Perturb {
+        network: &'a Network,
+        noise: f32,
+    },
+}
+
+impl Initializer<'_> {
+    /// Half-width of the range weights are drawn from, or `None` for
+    /// [`Initializer::Perturb`].
+    pub(crate) fn limit(&self, fan_in: usize, fan_out: usize) -> Option<f32> {
+        match self {
+            Self::Uniform => Some(1.0),
+            Self::Xavier => Some((6.0 / (fan_in + fan_out).max(1) as f32).sqrt()),
+            Self::He => Some((6.0 / fan_in.max(1) as f32).sqrt()),
+            Self::Zeros => Some(0.0),
+            Self::Perturb { .. } => None,
+        }
+    }
+}
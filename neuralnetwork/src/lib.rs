@@ -1,147 +1,547 @@
-use rand::Rng;
+pub use self::{activation::*, error::*, graph::*, init::*, persist::*};
+
+mod activation;
+mod error;
+mod graph;
+mod init;
+mod persist;
+
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone)]
 pub struct Network {
     layers: Vec<Layer>,
 }
 
+/// One layer of neurons along with the weights feeding into it; see
+/// [`Network::layers`].
 #[derive(Debug, Clone)]
-struct Layer {
-    neurons: Vec<Neuron>,
+pub struct Layer {
+    inputs: usize,
+    /// Row-major `biases.len() x inputs` matrix; row `i` holds the incoming
+    /// weights of neuron `i`.
+    weights: Vec<f32>,
+    /// Row-major `biases.len() x biases.len()` matrix of weights from the
+    /// layer's own previous outputs; empty unless the layer is recurrent.
+    recurrent: Vec<f32>,
+    biases: Vec<f32>,
+    activation: Activation,
+    kind: LayerKind,
 }
 
-#[derive(Debug, Clone)]
-struct Neuron {
-    weights: Vec<f32>,
+/// Read-only view of a single neuron of a [`Layer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Neuron<'a> {
     bias: f32,
+    weights: &'a [f32],
+    recurrent: &'a [f32],
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NetworkTopology {
     pub neurons: usize,
+    /// Applied to this layer's outputs; ignored for the input layer.
+    #[serde(default)]
+    pub activation: Activation,
+    /// Ignored for the input layer.
+    #[serde(default)]
+    pub kind: LayerKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LayerKind {
+    #[default]
+    Dense,
+    /// Elman layer: every neuron also sees the layer's outputs from the
+    /// previous step, kept in a [`NetworkState`].
+    Recurrent,
+}
+
+/// Reusable buffers for [`Network::propagate_into`].
+///
+/// Once they have grown to the widest layer, propagating through a network
+/// doesn't allocate.
+#[derive(Debug, Clone, Default)]
+pub struct Scratch {
+    front: Vec<f32>,
+    back: Vec<f32>,
+}
+
+/// Hidden state of a network's recurrent layers between two calls to
+/// [`Network::propagate_with_state`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkState {
+    /// Previous outputs of every layer; empty for dense layers.
+    layers: Vec<Vec<f32>>,
+}
+
+impl NetworkState {
+    /// Forgets everything, as if the network had never been run.
+    pub fn reset(&mut self) {
+        for layer in &mut self.layers {
+            layer.fill(0.0);
+        }
+    }
 }
 
 impl Network {
-    pub fn randomize(layers: &[NetworkTopology]) -> Self {
-        assert!(layers.len() > 1);
+    pub fn randomize(rng: &mut dyn RngCore, layers: &[NetworkTopology]) -> Self {
+        Self::try_randomize(rng, layers).unwrap_or_else(|err| panic!("{}", err))
+    }
+    pub fn try_randomize(
+        rng: &mut dyn RngCore,
+        layers: &[NetworkTopology],
+    ) -> Result<Self, NetworkError> {
+        Self::try_randomize_with(rng, layers, Initializer::Uniform)
+    }
+    pub fn randomize_with(
+        rng: &mut dyn RngCore,
+        layers: &[NetworkTopology],
+        initializer: Initializer,
+    ) -> Self {
+        Self::try_randomize_with(rng, layers, initializer).unwrap_or_else(|err| panic!("{}", err))
+    }
+    /// Fails on fewer than two layers, or if an [`Initializer::Perturb`]
+    /// template doesn't have the topology `layers` describes or its noise
+    /// is negative or not finite.
+    pub fn try_randomize_with(
+        rng: &mut dyn RngCore,
+        layers: &[NetworkTopology],
+        initializer: Initializer,
+    ) -> Result<Self, NetworkError> {
+        if layers.len() < 2 {
+            return Err(NetworkError::TooFewLayers(layers.len()));
+        }
+
+        if let Initializer::Perturb { network, noise } = initializer {
+            if !same_topology(layers, &network.topology()) {
+                return Err(NetworkError::TopologyMismatch);
+            }
+            if !(noise.is_finite() && noise >= 0.0) {
+                return Err(NetworkError::InvalidNoise);
+            }
+
+            let weights = network
+                .weights()
+                .into_iter()
+                .map(|weight| weight + rng.gen_range(-noise..=noise));
+
+            return Self::try_from_weights(layers, weights);
+        }
+
         let mut created_layers = Vec::new();
 
         for i in 0..(layers.len() - 1) {
-            let input_neurons = layers[i].neurons;
-            let output_neurons = layers[i + 1].neurons;
-
-            created_layers.push(Layer::randomize(input_neurons, output_neurons));
+            created_layers.push(Layer::randomize(
+                rng,
+                layers[i].neurons,
+                &layers[i + 1],
+                initializer,
+            ));
         }
 
-        Self {
+        Ok(Self {
             layers: created_layers,
+        })
+    }
+
+    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
+        self.try_propagate(inputs)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+    pub fn try_propagate(&self, inputs: Vec<f32>) -> Result<Vec<f32>, NetworkError> {
+        Ok(self
+            .try_propagate_into(&inputs, &mut Scratch::default())?
+            .to_vec())
+    }
+    /// Like [`Network::propagate`], but writes every layer's outputs into
+    /// `scratch` instead of allocating; the returned slice borrows from it.
+    ///
+    /// Recurrent layers behave as if their previous outputs were all zero;
+    /// use [`Network::propagate_with_state`] to carry them between calls.
+    pub fn propagate_into<'a>(&self, input: &[f32], scratch: &'a mut Scratch) -> &'a [f32] {
+        self.try_propagate_into(input, scratch)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+    pub fn try_propagate_into<'a>(
+        &self,
+        input: &[f32],
+        scratch: &'a mut Scratch,
+    ) -> Result<&'a [f32], NetworkError> {
+        self.check_input(input)?;
+
+        let Scratch { front, back } = scratch;
+
+        self.layers[0].propagate_into(input, None, front);
+
+        for layer in &self.layers[1..] {
+            layer.propagate_into(front, None, back);
+            std::mem::swap(front, back);
         }
+
+        Ok(front)
+    }
+    /// Like [`Network::propagate`], but returns the activations of every
+    /// layer, starting with `input` itself, so that `trace[i]` lines up with
+    /// `topology()[i]`.
+    ///
+    /// Recurrent layers see a zero previous state, as in
+    /// [`Network::propagate_into`].
+    pub fn propagate_traced(&self, input: &[f32]) -> Vec<Vec<f32>> {
+        self.try_propagate_traced(input)
+            .unwrap_or_else(|err| panic!("{}", err))
     }
+    pub fn try_propagate_traced(&self, input: &[f32]) -> Result<Vec<Vec<f32>>, NetworkError> {
+        self.check_input(input)?;
+
+        let mut trace = Vec::with_capacity(self.layers.len() + 1);
+        trace.push(input.to_vec());
 
-    pub fn propagate(&self, mut inputs: Vec<f32>) -> Vec<f32> {
         for layer in &self.layers {
-            inputs = layer.propagate(inputs);
+            let mut output = Vec::new();
+            layer.propagate_into(&trace[trace.len() - 1], None, &mut output);
+            trace.push(output);
+        }
+
+        Ok(trace)
+    }
+    /// Every layer except the input one, which has no neurons of its own.
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+    /// A blank state for [`Network::propagate_with_state`].
+    pub fn initial_state(&self) -> NetworkState {
+        NetworkState {
+            layers: self
+                .layers
+                .iter()
+                .map(|layer| match layer.kind {
+                    LayerKind::Dense => Vec::new(),
+                    LayerKind::Recurrent => vec![0.0; layer.biases.len()],
+                })
+                .collect(),
+        }
+    }
+    /// Like [`Network::propagate_into`], but feeds recurrent layers their
+    /// outputs from the previous call and records the new ones in `state`.
+    pub fn propagate_with_state<'a>(
+        &self,
+        input: &[f32],
+        state: &mut NetworkState,
+        scratch: &'a mut Scratch,
+    ) -> &'a [f32] {
+        self.try_propagate_with_state(input, state, scratch)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+    pub fn try_propagate_with_state<'a>(
+        &self,
+        input: &[f32],
+        state: &mut NetworkState,
+        scratch: &'a mut Scratch,
+    ) -> Result<&'a [f32], NetworkError> {
+        self.check_input(input)?;
+
+        if state.layers.len() != self.layers.len()
+            || self
+                .layers
+                .iter()
+                .zip(&state.layers)
+                .any(|(layer, previous)| layer.state_size() != previous.len())
+        {
+            return Err(NetworkError::StateMismatch);
+        }
+
+        let Scratch { front, back } = scratch;
+
+        for (i, (layer, previous)) in self.layers.iter().zip(&mut state.layers).enumerate() {
+            if i == 0 {
+                layer.propagate_into(input, Some(previous), front);
+            } else {
+                layer.propagate_into(front, Some(previous), back);
+                std::mem::swap(front, back);
+            }
+
+            if layer.kind == LayerKind::Recurrent {
+                previous.copy_from_slice(front);
+            }
         }
 
-        inputs
+        Ok(front)
+    }
+    fn check_input(&self, input: &[f32]) -> Result<(), NetworkError> {
+        let expected = self.layers[0].inputs;
+
+        if input.len() != expected {
+            return Err(NetworkError::InputSize {
+                expected,
+                found: input.len(),
+            });
+        }
+        Ok(())
     }
+    /// Reconstructs the topology this network was built from.
+    pub fn topology(&self) -> Vec<NetworkTopology> {
+        std::iter::once(NetworkTopology {
+            neurons: self.layers[0].inputs,
+            activation: Activation::Identity,
+            kind: LayerKind::Dense,
+        })
+        .chain(self.layers.iter().map(|layer| NetworkTopology {
+            neurons: layer.biases.len(),
+            activation: layer.activation,
+            kind: layer.kind,
+        }))
+        .collect()
+    }
+    /// Flattens the network as, for every neuron in order, its bias
+    /// followed by its incoming weights and, for recurrent layers, its
+    /// weights from the layer's previous outputs.
     pub fn weights(&self) -> Vec<f32> {
         let mut weights = Vec::new();
 
         for layer in &self.layers {
-            for neuron in &layer.neurons {
-                weights.push(neuron.bias);
-
-                for weight in &neuron.weights {
-                    weights.push(*weight);
-                }
+            for (i, (bias, row)) in layer.biases.iter().zip(layer.rows()).enumerate() {
+                weights.push(*bias);
+                weights.extend_from_slice(row);
+                weights.extend_from_slice(layer.recurrent_row(i));
             }
         }
         weights
     }
+    /// Number of weights (biases included) a network of this topology holds.
+    pub fn weight_count(layers: &[NetworkTopology]) -> usize {
+        Self::neuron_blocks(layers).into_iter().sum()
+    }
+    /// Length of every neuron's run of parameters in [`Network::weights`],
+    /// in order: its bias, incoming weights and recurrent weights.
+    pub fn neuron_blocks(layers: &[NetworkTopology]) -> Vec<usize> {
+        layers
+            .windows(2)
+            .flat_map(|layers| {
+                let recurrent = match layers[1].kind {
+                    LayerKind::Dense => 0,
+                    LayerKind::Recurrent => layers[1].neurons,
+                };
+
+                vec![layers[0].neurons + 1 + recurrent; layers[1].neurons]
+            })
+            .collect()
+    }
     pub fn from_weights(
         layers: &[NetworkTopology],
         weights: impl IntoIterator<Item = f32>,
     ) -> Self {
-        assert!(layers.len() > 1);
+        Self::try_from_weights(layers, weights).unwrap_or_else(|err| panic!("{}", err))
+    }
+    pub fn try_from_weights(
+        layers: &[NetworkTopology],
+        weights: impl IntoIterator<Item = f32>,
+    ) -> Result<Self, NetworkError> {
+        if layers.len() < 2 {
+            return Err(NetworkError::TooFewLayers(layers.len()));
+        }
+
+        let weights: Vec<f32> = weights.into_iter().collect();
+        let expected = Self::weight_count(layers);
+
+        if weights.len() != expected {
+            return Err(NetworkError::WeightCount {
+                expected,
+                found: weights.len(),
+            });
+        }
 
         let mut weights = weights.into_iter();
 
         let layers = layers
             .windows(2)
-            .map(|layers| Layer::from_weights(layers[0].neurons, layers[1].neurons, &mut weights))
+            .map(|layers| Layer::from_weights(layers[0].neurons, &layers[1], &mut weights))
             .collect();
 
-        if weights.next().is_some() {
-            panic!("got too many weights");
-        }
-
-        Self { layers }
+        Ok(Self { layers })
     }
 }
 
 impl Layer {
-    pub fn randomize(input_neurons: usize, output_neurons: usize) -> Self {
-        let neurons = (0..output_neurons)
-            .map(|_| Neuron::randomize(input_neurons))
-            .collect();
-
-        Self { neurons }
+    /// Size of the previous layer.
+    pub fn inputs(&self) -> usize {
+        self.inputs
     }
-
-    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
-        self.neurons
-            .iter()
-            .map(|neuron| neuron.propagate(&inputs))
-            .collect()
+    /// Number of neurons in this layer.
+    pub fn outputs(&self) -> usize {
+        self.biases.len()
     }
-    pub fn from_weights(
-        input_size: usize,
-        output_size: usize,
-        weights: &mut dyn Iterator<Item = f32>,
+    pub fn activation(&self) -> Activation {
+        self.activation
+    }
+    pub fn kind(&self) -> LayerKind {
+        self.kind
+    }
+    pub fn biases(&self) -> &[f32] {
+        &self.biases
+    }
+    /// Row-major `outputs() x inputs()` matrix; row `i` holds the incoming
+    /// weights of neuron `i`.
+    pub fn weights(&self) -> &[f32] {
+        &self.weights
+    }
+    /// Row-major `outputs() x outputs()` matrix of recurrent weights; empty
+    /// for dense layers.
+    pub fn recurrent_weights(&self) -> &[f32] {
+        &self.recurrent
+    }
+    pub fn neuron(&self, index: usize) -> Neuron<'_> {
+        Neuron {
+            bias: self.biases[index],
+            weights: &self.weights[index * self.inputs..(index + 1) * self.inputs],
+            recurrent: self.recurrent_row(index),
+        }
+    }
+    pub fn neurons(&self) -> impl Iterator<Item = Neuron<'_>> {
+        (0..self.outputs()).map(|index| self.neuron(index))
+    }
+
+    fn randomize(
+        rng: &mut dyn RngCore,
+        inputs: usize,
+        topology: &NetworkTopology,
+        initializer: Initializer,
     ) -> Self {
-        let neurons = (0..output_size)
-            .map(|_| Neuron::from_weights(input_size, weights))
-            .collect();
+        let fan_in = inputs
+            + match topology.kind {
+                LayerKind::Dense => 0,
+                LayerKind::Recurrent => topology.neurons,
+            };
+        let limit = initializer
+            .limit(fan_in, topology.neurons)
+            .expect("templates are handled by Network::try_randomize_with");
+
+        let mut layer = Self::build(inputs, topology, || {
+            if limit > 0.0 {
+                rng.gen_range(-limit..=limit)
+            } else {
+                0.0
+            }
+        });
 
-        Self { neurons }
+        if !matches!(initializer, Initializer::Uniform) {
+            layer.biases.fill(0.0);
+        }
+        layer
     }
-}
 
-impl Neuron {
-    pub fn randomize(output_size: usize) -> Self {
-        let mut rng = rand::thread_rng();
+    fn from_weights(
+        inputs: usize,
+        topology: &NetworkTopology,
+        weights: &mut dyn Iterator<Item = f32>,
+    ) -> Self {
+        Self::build(inputs, topology, || {
+            weights.next().expect("got not enough weights")
+        })
+    }
 
-        let bias = rng.gen_range(-1.0..=1.0);
+    /// Pulls every parameter from `next` in the order [`Network::weights`]
+    /// lays them out.
+    fn build(inputs: usize, topology: &NetworkTopology, mut next: impl FnMut() -> f32) -> Self {
+        let outputs = topology.neurons;
+        let recurrent_inputs = match topology.kind {
+            LayerKind::Dense => 0,
+            LayerKind::Recurrent => outputs,
+        };
+
+        let mut weights = Vec::with_capacity(inputs * outputs);
+        let mut recurrent = Vec::with_capacity(recurrent_inputs * outputs);
+        let mut biases = Vec::with_capacity(outputs);
+
+        for _ in 0..outputs {
+            biases.push(next());
+            weights.extend((0..inputs).map(|_| next()));
+            recurrent.extend((0..recurrent_inputs).map(|_| next()));
+        }
 
-        let weights: Vec<f32> = (0..output_size)
-            .map(|_| rng.gen_range(-1.0..=1.0))
-            .collect();
+        Self {
+            inputs,
+            weights,
+            recurrent,
+            biases,
+            activation: topology.activation,
+            kind: topology.kind,
+        }
+    }
 
-        Self { weights, bias }
+    fn rows(&self) -> impl Iterator<Item = &[f32]> {
+        (0..self.biases.len()).map(|i| &self.weights[i * self.inputs..(i + 1) * self.inputs])
     }
 
-    pub fn propagate(&self, inputs: &[f32]) -> f32 {
-        // assert!(inputs.len() == self.weights.len());
-        assert_eq!(inputs.len(), self.weights.len());
+    fn state_size(&self) -> usize {
+        match self.kind {
+            LayerKind::Dense => 0,
+            LayerKind::Recurrent => self.biases.len(),
+        }
+    }
 
-        let output = inputs
-            .iter()
-            .zip(&self.weights)
-            .map(|(input, weight)| input * weight)
-            .sum::<f32>();
+    fn recurrent_row(&self, neuron: usize) -> &[f32] {
+        let size = self.state_size();
 
-        (self.bias + output).max(0.0)
+        &self.recurrent[neuron * size..(neuron + 1) * size]
     }
-    pub fn from_weights(output_neurons: usize, weights: &mut dyn Iterator<Item = f32>) -> Self {
-        let bias = weights.next().expect("got not enough weights");
 
-        let weights = (0..output_neurons)
-            .map(|_| weights.next().expect("got not enough weights"))
-            .collect();
+    /// Overwrites `output` with this layer's activations; `input` must
+    /// already have been checked against `self.inputs`. Recurrent layers
+    /// read their previous outputs from `previous`, if given.
+    fn propagate_into(&self, input: &[f32], previous: Option<&[f32]>, output: &mut Vec<f32>) {
+        output.clear();
+        output.extend(
+            self.biases
+                .iter()
+                .zip(self.rows())
+                .enumerate()
+                .map(|(i, (bias, row))| {
+                    let mut sum = dot(row, input);
+
+                    if let Some(previous) = previous.filter(|_| self.kind == LayerKind::Recurrent) {
+                        sum += dot(self.recurrent_row(i), previous);
+                    }
+
+                    self.activation.apply(bias + sum)
+                }),
+        );
+    }
+}
 
-        Self { bias, weights }
+impl Neuron<'_> {
+    pub fn bias(&self) -> f32 {
+        self.bias
+    }
+    /// Incoming weights, one per neuron of the previous layer.
+    pub fn weights(&self) -> &[f32] {
+        self.weights
     }
+    /// Weights from the layer's previous outputs; empty for dense layers.
+    pub fn recurrent_weights(&self) -> &[f32] {
+        self.recurrent
+    }
+}
+
+/// Whether two topologies describe the same network. Compares layer sizes,
+/// activations and kinds, skipping the activation and kind of the input
+/// layer since they are never used.
+pub(crate) fn same_topology(expected: &[NetworkTopology], found: &[NetworkTopology]) -> bool {
+    expected.len() == found.len()
+        && expected
+            .iter()
+            .zip(found)
+            .enumerate()
+            .all(|(i, (expected, found))| {
+                expected.neurons == found.neurons
+                    && (i == 0
+                        || (expected.activation == found.activation && expected.kind == found.kind))
+            })
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
 }
 
 //-------------------------------------------
@@ -152,10 +552,176 @@ pub fn add(left: usize, right: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
 
     #[test]
     fn it_works() {
         let result = add(2, 2);
         assert_eq!(result, 4);
     }
+
+    fn topology(neurons: &[usize]) -> Vec<NetworkTopology> {
+        neurons
+            .iter()
+            .map(|&neurons| NetworkTopology {
+                neurons,
+                activation: Activation::Relu,
+                kind: LayerKind::Dense,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn try_from_weights_reports_wrong_weight_count() {
+        let topology = topology(&[2, 1]);
+
+        assert_eq!(
+            Network::try_from_weights(&topology, vec![0.0; 2]).unwrap_err(),
+            NetworkError::WeightCount {
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            Network::try_from_weights(&topology, vec![0.0; 4]).unwrap_err(),
+            NetworkError::WeightCount {
+                expected: 3,
+                found: 4
+            }
+        );
+    }
+
+    #[test]
+    fn try_propagate_reports_wrong_input_size() {
+        let network = Network::from_weights(&topology(&[2, 1]), vec![0.5, 1.0, 1.0]);
+
+        assert_eq!(network.try_propagate(vec![1.0, 2.0]), Ok(vec![3.5]));
+        assert_eq!(
+            network.try_propagate(vec![1.0]),
+            Err(NetworkError::InputSize {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn recurrent_layer_remembers_previous_output() {
+        let topology = [
+            NetworkTopology {
+                neurons: 1,
+                activation: Activation::Identity,
+                kind: LayerKind::Dense,
+            },
+            NetworkTopology {
+                neurons: 1,
+                activation: Activation::Identity,
+                kind: LayerKind::Recurrent,
+            },
+        ];
+        // bias, input weight, recurrent weight
+        let network = Network::from_weights(&topology, vec![0.0, 1.0, 0.5]);
+        let mut state = network.initial_state();
+        let mut scratch = Scratch::default();
+
+        assert_eq!(
+            network.propagate_with_state(&[2.0], &mut state, &mut scratch),
+            &[2.0]
+        );
+        assert_eq!(
+            network.propagate_with_state(&[2.0], &mut state, &mut scratch),
+            &[3.0]
+        );
+        assert_eq!(network.weights(), vec![0.0, 1.0, 0.5]);
+
+        state.reset();
+        assert_eq!(
+            network.propagate_with_state(&[2.0], &mut state, &mut scratch),
+            &[2.0]
+        );
+    }
+
+    #[test]
+    fn propagate_traced_returns_every_layer() {
+        // 1 -> 2 -> 1: hidden = [x + 1, 2x], output = hidden[0] - hidden[1]
+        let mut topology = topology(&[1, 2, 1]);
+        topology[2].activation = Activation::Identity;
+        let network = Network::from_weights(&topology, vec![1.0, 1.0, 0.0, 2.0, 0.0, 1.0, -1.0]);
+
+        let trace = network.propagate_traced(&[3.0]);
+
+        assert_eq!(trace, vec![vec![3.0], vec![4.0, 6.0], vec![-2.0]]);
+        assert_eq!(trace.last().unwrap(), &network.propagate(vec![3.0]));
+
+        let hidden = &network.layers()[0];
+        assert_eq!((hidden.inputs(), hidden.outputs()), (1, 2));
+        assert_eq!(hidden.biases(), &[1.0, 0.0]);
+        assert_eq!(hidden.neuron(1).weights(), &[2.0]);
+        assert!(hidden.neuron(1).recurrent_weights().is_empty());
+    }
+
+    #[test]
+    fn initializers_respect_fan_in() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let topology = topology(&[18, 36, 2]);
+
+        let he = Network::randomize_with(&mut rng, &topology, Initializer::He);
+        let limit = (6.0f32 / 18.0).sqrt();
+        assert!(he.layers()[0].weights().iter().all(|w| w.abs() <= limit));
+        assert!(he.layers()[0].biases().iter().all(|&b| b == 0.0));
+
+        let zeros = Network::randomize_with(&mut rng, &topology, Initializer::Zeros);
+        assert!(zeros.weights().iter().all(|&w| w == 0.0));
+
+        let perturbed = Network::randomize_with(
+            &mut rng,
+            &topology,
+            Initializer::Perturb {
+                network: &he,
+                noise: 0.1,
+            },
+        );
+        assert!(perturbed
+            .weights()
+            .iter()
+            .zip(he.weights())
+            .all(|(a, b)| (a - b).abs() <= 0.1 + f32::EPSILON));
+
+        assert_eq!(
+            Network::try_randomize_with(
+                &mut rng,
+                &topology[..2],
+                Initializer::Perturb {
+                    network: &he,
+                    noise: 0.1
+                }
+            )
+            .unwrap_err(),
+            NetworkError::TopologyMismatch
+        );
+
+        for noise in [-0.1, f32::NAN, f32::INFINITY] {
+            assert_eq!(
+                Network::try_randomize_with(
+                    &mut rng,
+                    &topology,
+                    Initializer::Perturb {
+                        network: &he,
+                        noise
+                    }
+                )
+                .unwrap_err(),
+                NetworkError::InvalidNoise
+            );
+        }
+    }
+
+    #[test]
+    fn try_randomize_rejects_single_layer() {
+        assert_eq!(
+            Network::try_randomize(&mut ChaCha8Rng::seed_from_u64(0), &topology(&[2])).unwrap_err(),
+            NetworkError::TooFewLayers(1)
+        );
+    }
 }
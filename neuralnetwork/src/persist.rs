@@ -0,0 +1,268 @@
+use std::{fmt, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{same_topology, Activation, LayerKind, Network, NetworkError, NetworkTopology};
+
+/// Version written into every saved network. Bump it whenever the layout of
+/// [`SavedNetwork`] changes.
+///
+/// - 1: topology and weights, every layer implicitly ReLU
+/// - 2: topology carries per-layer activations
+/// - 3: topology carries per-layer kinds (dense or recurrent)
+pub const FORMAT_VERSION: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Binary,
+}
+
+#[derive(Debug)]
+pub enum PersistError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Binary(bincode::Error),
+    UnsupportedVersion(u32),
+    TopologyMismatch {
+        expected: Vec<NetworkTopology>,
+        found: Vec<NetworkTopology>,
+    },
+    Network(NetworkError),
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "i/o error: {}", err),
+            Self::Json(err) => write!(f, "invalid json network: {}", err),
+            Self::Binary(err) => write!(f, "invalid binary network: {}", err),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported network format version {}", version)
+            }
+            Self::TopologyMismatch { expected, found } => write!(
+                f,
+                "topology mismatch: expected {:?}, found {:?}",
+                expected, found
+            ),
+            Self::Network(err) => write!(f, "invalid network: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for PersistError {}
+
+impl From<io::Error> for PersistError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<NetworkError> for PersistError {
+    fn from(err: NetworkError) -> Self {
+        Self::Network(err)
+    }
+}
+
+impl From<serde_json::Error> for PersistError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+impl From<bincode::Error> for PersistError {
+    fn from(err: bincode::Error) -> Self {
+        Self::Binary(err)
+    }
+}
+
+/// On-disk representation of a [`Network`]: the topology plus the flat
+/// weights as laid out by [`Network::weights`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedNetwork {
+    version: u32,
+    topology: Vec<NetworkTopology>,
+    weights: Vec<f32>,
+}
+
+/// Binary layout of format versions 1 and 2, which differ only in their
+/// topology. JSON files of those versions decode straight into
+/// [`SavedNetwork`], since missing fields fall back to their defaults.
+#[derive(Deserialize)]
+struct LegacySavedNetwork<T> {
+    version: u32,
+    topology: Vec<T>,
+    weights: Vec<f32>,
+}
+
+#[derive(Deserialize)]
+struct TopologyV1 {
+    neurons: usize,
+}
+
+#[derive(Deserialize)]
+struct TopologyV2 {
+    neurons: usize,
+    activation: Activation,
+}
+
+impl From<TopologyV1> for NetworkTopology {
+    fn from(layer: TopologyV1) -> Self {
+        Self {
+            neurons: layer.neurons,
+            activation: Activation::Relu,
+            kind: LayerKind::Dense,
+        }
+    }
+}
+
+impl From<TopologyV2> for NetworkTopology {
+    fn from(layer: TopologyV2) -> Self {
+        Self {
+            neurons: layer.neurons,
+            activation: layer.activation,
+            kind: LayerKind::Dense,
+        }
+    }
+}
+
+impl<T: Into<NetworkTopology>> From<LegacySavedNetwork<T>> for SavedNetwork {
+    fn from(saved: LegacySavedNetwork<T>) -> Self {
+        Self {
+            version: saved.version,
+            topology: saved.topology.into_iter().map(Into::into).collect(),
+            weights: saved.weights,
+        }
+    }
+}
+
+impl SavedNetwork {
+    fn decode(bytes: &[u8], format: Format) -> Result<Self, PersistError> {
+        match format {
+            Format::Json => Ok(serde_json::from_slice(bytes)?),
+            Format::Binary => match bincode::deserialize::<u32>(bytes)? {
+                1 => Ok(bincode::deserialize::<LegacySavedNetwork<TopologyV1>>(bytes)?.into()),
+                2 => Ok(bincode::deserialize::<LegacySavedNetwork<TopologyV2>>(bytes)?.into()),
+                FORMAT_VERSION => Ok(bincode::deserialize(bytes)?),
+                // Unknown layouts can't be decoded any further.
+                version => Err(PersistError::UnsupportedVersion(version)),
+            },
+        }
+    }
+}
+
+impl Network {
+    pub fn to_bytes(&self, format: Format) -> Result<Vec<u8>, PersistError> {
+        let saved = SavedNetwork {
+            version: FORMAT_VERSION,
+            topology: self.topology(),
+            weights: self.weights(),
+        };
+
+        Ok(match format {
+            Format::Json => serde_json::to_vec_pretty(&saved)?,
+            Format::Binary => bincode::serialize(&saved)?,
+        })
+    }
+
+    /// Decodes a network and checks that it was saved with `topology`.
+    pub fn from_bytes(
+        bytes: &[u8],
+        format: Format,
+        topology: &[NetworkTopology],
+    ) -> Result<Self, PersistError> {
+        let saved = SavedNetwork::decode(bytes, format)?;
+
+        if !(1..=FORMAT_VERSION).contains(&saved.version) {
+            return Err(PersistError::UnsupportedVersion(saved.version));
+        }
+        if !same_topology(topology, &saved.topology) {
+            return Err(PersistError::TopologyMismatch {
+                expected: topology.to_vec(),
+                found: saved.topology,
+            });
+        }
+
+        Ok(Self::try_from_weights(topology, saved.weights)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>, format: Format) -> Result<(), PersistError> {
+        fs::write(path, self.to_bytes(format)?)?;
+        Ok(())
+    }
+
+    pub fn load(
+        path: impl AsRef<Path>,
+        format: Format,
+        topology: &[NetworkTopology],
+    ) -> Result<Self, PersistError> {
+        Self::from_bytes(&fs::read(path)?, format, topology)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn topology(neurons: &[usize]) -> Vec<NetworkTopology> {
+        neurons
+            .iter()
+            .map(|&neurons| NetworkTopology {
+                neurons,
+                activation: Activation::Tanh,
+                kind: LayerKind::Dense,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_in_both_formats() {
+        let topology = topology(&[3, 4, 2]);
+        let network = Network::randomize(&mut ChaCha8Rng::seed_from_u64(0), &topology);
+
+        for format in [Format::Json, Format::Binary] {
+            let bytes = network.to_bytes(format).unwrap();
+            let loaded = Network::from_bytes(&bytes, format, &topology).unwrap();
+
+            assert_eq!(loaded.weights(), network.weights());
+        }
+    }
+
+    #[test]
+    fn rejects_mismatched_topology() {
+        let network = Network::randomize(&mut ChaCha8Rng::seed_from_u64(0), &topology(&[3, 4, 2]));
+        let bytes = network.to_bytes(Format::Binary).unwrap();
+
+        assert!(matches!(
+            Network::from_bytes(&bytes, Format::Binary, &topology(&[3, 5, 2])),
+            Err(PersistError::TopologyMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_mismatched_activation() {
+        let saved = topology(&[3, 4, 2]);
+        let network = Network::randomize(&mut ChaCha8Rng::seed_from_u64(0), &saved);
+        let bytes = network.to_bytes(Format::Json).unwrap();
+
+        let mut expected = saved.clone();
+        expected[2].activation = Activation::Relu;
+
+        assert!(matches!(
+            Network::from_bytes(&bytes, Format::Json, &expected),
+            Err(PersistError::TopologyMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_future_binary_versions() {
+        let bytes = bincode::serialize(&(FORMAT_VERSION + 1, "some future layout")).unwrap();
+
+        assert!(matches!(
+            Network::from_bytes(&bytes, Format::Binary, &topology(&[3, 4, 2])),
+            Err(PersistError::UnsupportedVersion(version)) if version == FORMAT_VERSION + 1
+        ));
+    }
+}
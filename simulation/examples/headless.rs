@@ -0,0 +1,41 @@
+//! Runs the simulation without a window and exports per-generation
+//! statistics.
+//!
+//! ```text
+//! cargo run --release -p lib-simulation --example headless -- [generations] [seed] [output]
+//! ```
+//!
+//! Writes `<output>.csv` and `<output>.json` (default output: `statistics`).
+
+use std::{env, error::Error, fs::File, io::BufWriter};
+
+use lib_genetic_algo::StatisticsHistory;
+use lib_simulation::{Simulation, SimulationConfig};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args().skip(1);
+    let generations: usize = args.next().map_or(Ok(10), |arg| arg.parse())?;
+    let seed: u64 = args.next().map_or(Ok(0), |arg| arg.parse())?;
+    let output = args.next().unwrap_or_else(|| "statistics".to_owned());
+
+    let mut simulation = Simulation::from_seed(seed, SimulationConfig::default());
+    let mut history = StatisticsHistory::new();
+
+    while history.len() < generations {
+        if let Some(statistics) = simulation.step_forward() {
+            println!(
+                "generation {}: min {} max {} avg {:.2} diversity {:.3}",
+                history.len(),
+                statistics.min_fitness,
+                statistics.max_fitness,
+                statistics.avg_fitness,
+                statistics.diversity,
+            );
+            history.push(statistics);
+        }
+    }
+
+    history.write_csv(BufWriter::new(File::create(format!("{}.csv", output))?))?;
+    history.write_json(BufWriter::new(File::create(format!("{}.json", output))?))?;
+    Ok(())
+}
@@ -1,33 +1,159 @@
 use crate::*;
+use lib_genetic_algo::neat::NeatGenome;
+use std::{io, path::Path};
 
 #[derive(Debug, Clone)]
-pub struct Brain {
-    pub nn: lib_neuralnetwork::Network,
+pub enum Brain {
+    /// Layered network whose shape comes from [`Brain::topology`].
+    Fixed(lib_neuralnetwork::Network),
+    /// Network grown by NEAT, along with the genome it was built from.
+    Neat {
+        genome: NeatGenome,
+        nn: GraphNetwork,
+    },
 }
 
 impl Brain {
-    pub fn randomize(vision: &Vision) -> Self {
-        Self {
-            nn: lib_neuralnetwork::Network::randomize(&Self::topology(vision)),
+    pub fn randomize(
+        rng: &mut dyn RngCore,
+        vision: &Vision,
+        kind: BrainKind,
+        init: WeightInit,
+    ) -> Self {
+        match kind {
+            BrainKind::Neat => Self::from_genome(NeatGenome::minimal(rng, vision.cells(), 2))
+                .expect("minimal genomes have no hidden nodes to form a cycle"),
+            _ => Self::Fixed(lib_neuralnetwork::Network::randomize_with(
+                rng,
+                &Self::topology(vision, kind),
+                init.into(),
+            )),
         }
     }
     pub fn as_chromosome(&self) -> lib_genetic_algo::Chromosome {
-        self.nn.weights().into_iter().collect()
+        match self {
+            Self::Fixed(nn) => nn.weights().into_iter().collect(),
+            Self::Neat { genome, .. } => genome
+                .connections()
+                .iter()
+                .map(|connection| connection.weight)
+                .collect(),
+        }
     }
-    pub fn topology(vision: &Vision) -> [lib_neuralnetwork::NetworkTopology; 3] {
+    pub fn topology(vision: &Vision, kind: BrainKind) -> [lib_neuralnetwork::NetworkTopology; 3] {
         [
             lib_neuralnetwork::NetworkTopology {
                 neurons: vision.cells(),
+                activation: Activation::Identity,
+                kind: LayerKind::Dense,
             },
             lib_neuralnetwork::NetworkTopology {
                 neurons: vision.cells() * 2,
+                activation: Activation::Relu,
+                kind: match kind {
+                    BrainKind::Recurrent => LayerKind::Recurrent,
+                    _ => LayerKind::Dense,
+                },
+            },
+            // tanh lets the ants both speed up and slow down, and turn
+            // either way.
+            lib_neuralnetwork::NetworkTopology {
+                neurons: 2,
+                activation: Activation::Tanh,
+                kind: LayerKind::Dense,
             },
-            lib_neuralnetwork::NetworkTopology { neurons: 2 },
         ]
     }
-    pub fn from_chromosome(chromosome: Chromosome, vision: &Vision) -> Self {
-        Self {
-            nn: Network::from_weights(&Self::topology(vision), chromosome),
+    /// Fails if the genome doesn't hold exactly the weights the topology
+    /// for `vision` and `kind` needs. Always builds a fixed brain; NEAT
+    /// brains come from [`Brain::from_genome`].
+    pub fn from_chromosome(
+        chromosome: Chromosome,
+        vision: &Vision,
+        kind: BrainKind,
+    ) -> Result<Self, NetworkError> {
+        Ok(Self::Fixed(Network::try_from_weights(
+            &Self::topology(vision, kind),
+            chromosome,
+        )?))
+    }
+    /// Builds the network described by `genome`, skipping its disabled
+    /// connections.
+    pub fn from_genome(genome: NeatGenome) -> Result<Self, NetworkError> {
+        let nodes: Vec<_> = genome
+            .nodes()
+            .iter()
+            .map(|node| GraphNode {
+                id: node.id,
+                bias: node.bias,
+            })
+            .collect();
+        let edges: Vec<_> = genome
+            .connections()
+            .iter()
+            .filter(|connection| connection.enabled)
+            .map(|connection| GraphEdge {
+                from: connection.from,
+                to: connection.to,
+                weight: connection.weight,
+            })
+            .collect();
+        let nn = GraphNetwork::try_new(
+            genome.inputs(),
+            genome.outputs(),
+            &nodes,
+            &edges,
+            Activation::Tanh,
+            Activation::Tanh,
+        )?;
+
+        Ok(Self::Neat { genome, nn })
+    }
+    pub fn genome(&self) -> Option<&NeatGenome> {
+        match self {
+            Self::Fixed(_) => None,
+            Self::Neat { genome, .. } => Some(genome),
         }
     }
+    pub fn initial_state(&self) -> NetworkState {
+        match self {
+            Self::Fixed(nn) => nn.initial_state(),
+            Self::Neat { .. } => NetworkState::default(),
+        }
+    }
+    pub fn propagate<'a>(
+        &self,
+        input: &[f32],
+        memory: &mut NetworkState,
+        scratch: &'a mut Scratch,
+    ) -> &'a [f32] {
+        match self {
+            Self::Fixed(nn) => nn.propagate_with_state(input, memory, scratch),
+            Self::Neat { nn, .. } => nn.propagate_into(input, scratch),
+        }
+    }
+    /// Only fixed brains can be saved for now.
+    pub fn save(&self, path: impl AsRef<Path>, format: Format) -> Result<(), PersistError> {
+        match self {
+            Self::Fixed(nn) => nn.save(path, format),
+            Self::Neat { .. } => Err(PersistError::Io(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "NEAT brains can't be saved",
+            ))),
+        }
+    }
+    /// Loads a saved brain, failing if it wasn't built for `vision` and
+    /// `kind`.
+    pub fn load(
+        path: impl AsRef<Path>,
+        format: Format,
+        vision: &Vision,
+        kind: BrainKind,
+    ) -> Result<Self, PersistError> {
+        Ok(Self::Fixed(Network::load(
+            path,
+            format,
+            &Self::topology(vision, kind),
+        )?))
+    }
 }
@@ -0,0 +1,182 @@
+use lib_genetic_algo::{BoundPolicy, MigrationTopology};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+/// Tunable parameters of a simulation run.
+///
+/// Every field has a default matching the original hard-coded values, so a
+/// config file only needs to list the parameters it changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulationConfig {
+    pub population_size: usize,
+    pub food_count: usize,
+    pub generation_length: usize,
+    pub vision_range: f32,
+    pub vision_angle: f32,
+    pub vision_cells: usize,
+    pub speed_min: f32,
+    pub speed_max: f32,
+    pub speed_accel: f32,
+    pub rotation_accel: f32,
+    pub eat_range: f32,
+    pub brain: BrainKind,
+    /// How the fixed brains of the first generation are drawn.
+    pub weight_init: WeightInit,
+    /// Number of best ants copied unchanged into the next generation.
+    pub elite_count: usize,
+    /// Chance of mutating each weight of a child.
+    pub mutation_chance: f32,
+    /// Standard deviation of weight mutations, or its starting value when
+    /// `mutation_schedule` changes it over time.
+    pub mutation_coefficient: f32,
+    pub mutation_schedule: MutationSchedule,
+    /// Splits fixed-brain ants into species of genomes within this
+    /// distance of each other, so a single strategy can't take over.
+    pub speciation_threshold: Option<f32>,
+    /// Number of worlds evolving side by side.
+    pub islands: usize,
+    /// Generations between two exchanges of champions between islands.
+    pub migration_interval: usize,
+    /// Champions each island sends to each of its neighbours.
+    pub migration_size: usize,
+    pub migration_topology: MigrationTopology,
+    /// Evolves fixed-brain ants with NSGA-II on these objectives instead of
+    /// on food alone when not empty.
+    pub objectives: Vec<Objective>,
+    /// Rewards fixed-brain ants for exploring unlike the others.
+    pub novelty: Option<NoveltyConfig>,
+    /// Number of best ants of the whole run to remember.
+    pub hall_of_fame_size: usize,
+    /// Replaces a few ants at a time instead of the whole population once
+    /// per generation, so the world never resets. Novelty, migration and
+    /// the mutation schedule apply as usual, each replacement counting as a
+    /// generation. Ignored for NEAT brains and when `objectives` are set.
+    pub steady_state: Option<SteadyState>,
+    /// Keeps the brain weights of fixed-brain ants within this distance of
+    /// zero after every crossover and mutation.
+    pub weight_bound: Option<f32>,
+    /// How weights past `weight_bound` are brought back.
+    pub bound_policy: BoundPolicy,
+    /// Records the family tree of fixed-brain ants in
+    /// [`Simulation::lineage`](crate::Simulation::lineage).
+    pub track_lineage: bool,
+}
+
+/// Shape of the network driving each animal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BrainKind {
+    /// Feed-forward network: reacts only to the current vision frame.
+    #[default]
+    FeedForward,
+    /// Recurrent hidden layer, so animals can remember what they saw on
+    /// previous steps.
+    Recurrent,
+    /// Topology evolved by NEAT, starting from inputs wired straight to the
+    /// outputs.
+    Neat,
+}
+
+/// Settings of steady-state evolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SteadyState {
+    /// Steps between two replacements.
+    pub interval: usize,
+    /// Ants replaced each time.
+    pub count: usize,
+}
+
+/// Settings of [`lib_genetic_algo::NoveltySearch`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NoveltyConfig {
+    /// Number of nearest neighbours novelty is averaged over.
+    pub k: usize,
+    /// Novelty an ant's behavior needs to be archived.
+    pub threshold: f32,
+    /// Fitness an ant gains per unit of novelty, where one piece of food is
+    /// worth one.
+    pub weight: f32,
+}
+
+/// Something NSGA-II trades off against the other objectives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Objective {
+    /// Maximize the food eaten.
+    Food,
+    /// Minimize the distance travelled.
+    Energy,
+    /// Minimize the steps spent touching obstacles.
+    Collisions,
+}
+
+/// How the mutation strength evolves over a run.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum MutationSchedule {
+    #[default]
+    Fixed,
+    /// Linear decay to `end` over `generations`.
+    Linear { end: f32, generations: usize },
+    /// Multiplied by `rate` every generation, down to `min`.
+    Exponential { rate: f32, min: f32 },
+    /// Rechenberg's 1/5th success rule, driven by the best fitness.
+    OneFifthRule,
+    /// Every ant carries its own mutation strength as an extra gene.
+    SelfAdaptive,
+}
+
+/// Serializable subset of [`lib_neuralnetwork::Initializer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WeightInit {
+    #[default]
+    Uniform,
+    Xavier,
+    He,
+    Zeros,
+}
+
+impl From<WeightInit> for lib_neuralnetwork::Initializer<'static> {
+    fn from(init: WeightInit) -> Self {
+        match init {
+            WeightInit::Uniform => Self::Uniform,
+            WeightInit::Xavier => Self::Xavier,
+            WeightInit::He => Self::He,
+            WeightInit::Zeros => Self::Zeros,
+        }
+    }
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            population_size: 50,
+            food_count: 30,
+            generation_length: 2000,
+            vision_range: 0.25,
+            vision_angle: PI + FRAC_PI_4,
+            vision_cells: 18,
+            speed_min: 0.001,
+            speed_max: 0.005,
+            speed_accel: 0.2,
+            rotation_accel: FRAC_PI_2,
+            eat_range: 0.009,
+            brain: BrainKind::default(),
+            weight_init: WeightInit::default(),
+            elite_count: 0,
+            mutation_chance: 0.01,
+            mutation_coefficient: 0.3,
+            mutation_schedule: MutationSchedule::default(),
+            speciation_threshold: None,
+            islands: 1,
+            migration_interval: 10,
+            migration_size: 1,
+            migration_topology: MigrationTopology::default(),
+            objectives: Vec::new(),
+            novelty: None,
+            hall_of_fame_size: 10,
+            steady_state: None,
+            weight_bound: None,
+            bound_policy: BoundPolicy::default(),
+            track_lineage: false,
+        }
+    }
+}
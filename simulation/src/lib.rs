@@ -1,29 +1,46 @@
+use lib_genetic_algo::neat::{Neat, NeatConfig, NeatGenome, NeatIndividual};
 use lib_genetic_algo::*;
 use lib_genetic_algo::{Chromosome, Individual, RouletteWheel, UniformCrossOver};
 use lib_neuralnetwork::*;
-use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+use std::f32::consts::PI;
 mod brain;
+mod config;
 
 use brain::*;
+pub use config::*;
 use na::{Point2, Rotation2};
 use nalgebra as na;
-use rand::{Rng, RngCore};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
-const VISION_RANGE: f32 = 0.25;
-const VISION_ANGLE: f32 = PI + FRAC_PI_4;
-const CELLS: usize = 18;
-const SPEED_MIN: f32 = 0.001;
-const SPEED_MAX: f32 = 0.005;
-const SPEED_ACCEL: f32 = 0.2;
-const ROTATION_ACCEL: f32 = FRAC_PI_2;
-
-const GENERATION_LENGTH: usize = 2000;
 const NUM_OBSTACLES: usize = 400;
+/// Side of the grid [`Animal::visited`] counts steps in.
+const VISITED_GRID: usize = 4;
 
 pub struct Simulation {
-    pub world: World,
-    pub ga: GeneticAlgorithm<RouletteWheel>,
+    /// One world per island, each with its own population.
+    pub worlds: Vec<World>,
+    pub ga: IslandModel<RouletteWheel>,
+    /// One per island; evolves the populations instead of `ga` when the
+    /// config asks for [`BrainKind::Neat`].
+    pub neat: Vec<Neat>,
+    /// One per island; evolves the populations instead of `ga` when the
+    /// config lists [`Objective`]s.
+    pub nsga2: Vec<Nsga2>,
+    /// Adds the novelty of each fixed-brain ant to its fitness when the
+    /// config enables it; the archive is shared by all islands.
+    pub novelty: Option<NoveltySearch>,
+    /// Best fixed-brain ants of the whole run, by food eaten.
+    pub hall_of_fame: HallOfFame,
+    /// Every fixed-brain ant evaluated so far with its parents and food
+    /// eaten, when the config asks to track lineage.
+    pub lineage: LineageGraph,
     pub age: usize,
+    pub config: SimulationConfig,
+    rng: ChaCha8Rng,
+    scratch: Scratch,
+    island_statistics: Vec<Statistics>,
+    pareto_statistics: Vec<ParetoStatistics>,
 }
 
 #[derive(Debug, Clone)]
@@ -40,7 +57,25 @@ pub struct Animal {
     pub speed: f32,
     pub vision: Vision,
     pub brain: Brain,
+    /// Hidden state of the brain's recurrent layers, if any.
+    pub memory: NetworkState,
+    /// Genes inherited alongside the brain's weights that tune evolution
+    /// itself, i.e. the mutation strength under
+    /// [`MutationSchedule::SelfAdaptive`]; empty otherwise.
+    pub strategy: Vec<f32>,
     pub(crate) score: usize,
+    /// Distance travelled this generation.
+    pub(crate) energy: f32,
+    /// Steps spent touching an obstacle this generation.
+    pub(crate) collisions: usize,
+    /// Steps since the ant was born.
+    pub(crate) lifetime: usize,
+    /// Fraction of the generation spent in each cell of a coarse grid over
+    /// the world, row by row; the ant's behavior for novelty search.
+    pub(crate) visited: Vec<f32>,
+    /// Identity and parents of a fixed-brain ant, given by the genetic
+    /// algorithm; `None` until the ant first takes part in evolution.
+    pub lineage: Option<Lineage>,
     pub vision_input: Vec<f32>,
 }
 #[derive(Debug, Clone)]
@@ -51,6 +86,7 @@ pub struct Obstacle {
 pub struct AnimalIndividual {
     pub fitness: f32,
     pub chromosome: Chromosome,
+    pub lineage: Option<Lineage>,
 }
 
 impl AnimalIndividual {
@@ -58,10 +94,13 @@ impl AnimalIndividual {
         Self {
             fitness: animal.score as f32,
             chromosome: animal.as_chromosome(),
+            lineage: animal.lineage.clone(),
         }
     }
-    pub fn to_animal(self, rng: &mut dyn RngCore) -> Animal {
-        Animal::from_chromosome(self.chromosome, rng)
+    pub fn to_animal(self, rng: &mut dyn RngCore, config: &SimulationConfig) -> Animal {
+        let mut animal = Animal::from_chromosome(self.chromosome, rng, config);
+        animal.lineage = self.lineage;
+        animal
     }
 }
 
@@ -70,6 +109,7 @@ impl Individual for AnimalIndividual {
         Self {
             fitness: 0.0,
             chromosome,
+            lineage: None,
         }
     }
 
@@ -80,6 +120,95 @@ impl Individual for AnimalIndividual {
     fn fitness(&self) -> f32 {
         self.fitness
     }
+
+    fn lineage(&self) -> Option<&Lineage> {
+        self.lineage.as_ref()
+    }
+
+    fn set_lineage(&mut self, lineage: Lineage) {
+        self.lineage = Some(lineage);
+    }
+}
+
+/// Ant judged on the config's [`Objective`]s, for NSGA-II.
+pub struct MultiObjectiveAnimalIndividual {
+    pub objectives: Vec<f32>,
+    pub chromosome: Chromosome,
+}
+
+impl MultiObjectiveAnimalIndividual {
+    pub fn from_animal(animal: &Animal, objectives: &[Objective]) -> Self {
+        Self {
+            objectives: objectives
+                .iter()
+                .map(|objective| match objective {
+                    Objective::Food => animal.score as f32,
+                    Objective::Energy => -animal.energy,
+                    Objective::Collisions => -(animal.collisions as f32),
+                })
+                .collect(),
+            chromosome: animal.as_chromosome(),
+        }
+    }
+    pub fn to_animal(self, rng: &mut dyn RngCore, config: &SimulationConfig) -> Animal {
+        Animal::from_chromosome(self.chromosome, rng, config)
+    }
+}
+
+impl MultiObjectiveIndividual for MultiObjectiveAnimalIndividual {
+    fn create(chromosome: Chromosome) -> Self {
+        Self {
+            objectives: Vec::new(),
+            chromosome,
+        }
+    }
+
+    fn chromosome(&self) -> &Chromosome {
+        &self.chromosome
+    }
+
+    fn objectives(&self) -> Vec<f32> {
+        self.objectives.clone()
+    }
+}
+
+pub struct NeatAnimalIndividual {
+    pub fitness: f32,
+    pub genome: NeatGenome,
+}
+
+impl NeatAnimalIndividual {
+    /// `animal` must have a NEAT brain.
+    pub fn from_animal(animal: &Animal) -> Self {
+        Self {
+            fitness: animal.score as f32,
+            genome: animal
+                .brain
+                .genome()
+                .expect("animal has a NEAT brain")
+                .clone(),
+        }
+    }
+    pub fn to_animal(self, rng: &mut dyn RngCore, config: &SimulationConfig) -> Animal {
+        Animal::from_genome(self.genome, rng, config)
+    }
+}
+
+impl NeatIndividual for NeatAnimalIndividual {
+    fn create(genome: NeatGenome) -> Self {
+        Self {
+            fitness: 0.0,
+            genome,
+        }
+    }
+
+    fn genome(&self) -> &NeatGenome {
+        &self.genome
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -101,6 +230,13 @@ impl Vision {
             cells,
         }
     }
+    pub fn from_config(config: &SimulationConfig) -> Self {
+        Self::new(
+            config.vision_range,
+            config.vision_angle,
+            config.vision_cells,
+        )
+    }
     pub fn cells(&self) -> usize {
         self.cells
     }
@@ -111,7 +247,22 @@ impl Vision {
         food: &[Food],
         obstacles: &[Obstacle],
     ) -> Vec<f32> {
-        let mut cells = vec![0.0; self.cells];
+        let mut cells = Vec::new();
+        self.process_vision_into(position, rotation, food, obstacles, &mut cells);
+        cells
+    }
+    /// Like [`Vision::process_vision`], but reuses `cells` instead of
+    /// allocating a new buffer.
+    pub fn process_vision_into(
+        &self,
+        position: Point2<f32>,
+        rotation: Rotation2<f32>,
+        food: &[Food],
+        obstacles: &[Obstacle],
+        cells: &mut Vec<f32>,
+    ) {
+        cells.clear();
+        cells.resize(self.cells, 0.0);
 
         for food in food {
             let vector = food.pos - position;
@@ -155,121 +306,418 @@ impl Vision {
 
             // cells[cell] += (self.range - distance) / self.range;
         }
-        cells
     }
 }
 
 impl Default for Vision {
     fn default() -> Self {
-        Self {
-            range: VISION_RANGE,
-            angle: VISION_ANGLE,
-            cells: CELLS,
-        }
+        Self::from_config(&SimulationConfig::default())
+    }
+}
+
+fn mutation(config: &SimulationConfig) -> Box<dyn Mutation> {
+    let (chance, sigma) = (config.mutation_chance, config.mutation_coefficient);
+
+    match config.mutation_schedule {
+        MutationSchedule::Fixed => Box::new(GaussianMutation::new(chance, sigma)),
+        MutationSchedule::Linear { end, generations } => Box::new(ScheduledMutation::new(
+            chance,
+            sigma,
+            Schedule::Linear { end, generations },
+        )),
+        MutationSchedule::Exponential { rate, min } => Box::new(ScheduledMutation::new(
+            chance,
+            sigma,
+            Schedule::Exponential { rate, min },
+        )),
+        MutationSchedule::OneFifthRule => Box::new(OneFifthRuleMutation::new(chance, sigma)),
+        MutationSchedule::SelfAdaptive => Box::new(SelfAdaptiveMutation::new(chance, 1e-3)),
+    }
+}
+
+/// Bounds of the brain weights of fixed-brain ants, if the config sets
+/// any; strategy genes, which come after the weights, stay unbounded.
+fn bounds(config: &SimulationConfig) -> Option<Bounds> {
+    let bound = config.weight_bound?;
+    let topology = Brain::topology(&Vision::from_config(config), config.brain);
+
+    Some(Bounds::new(config.bound_policy).with(Network::weight_count(&topology), -bound..=bound))
+}
+
+/// Initial strategy genes of an ant; see [`Animal::strategy`].
+fn initial_strategy(config: &SimulationConfig) -> Vec<f32> {
+    match config.mutation_schedule {
+        MutationSchedule::SelfAdaptive => vec![config.mutation_coefficient],
+        _ => Vec::new(),
     }
 }
 
 impl Simulation {
-    pub fn randomize(rng: &mut dyn RngCore) -> Self {
-        let world = World::randomize(rng);
-        let ga = GeneticAlgorithm::new(
-            RouletteWheel::default(),
-            UniformCrossOver::default(),
-            GaussianMutation::new(0.01, 0.3),
+    /// Builds a simulation whose worlds and all later random draws (motion,
+    /// food respawns, evolution) are derived from `rng`.
+    pub fn randomize(rng: &mut dyn RngCore, config: SimulationConfig) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(rng.next_u64());
+        let islands = config.islands.max(1);
+        let worlds = (0..islands)
+            .map(|_| World::randomize(&mut rng, &config))
+            .collect();
+        let ga = IslandModel::new(
+            (0..islands)
+                .map(|_| {
+                    let mut ga =
+                        GeneticAlgorithm::new(RouletteWheel, UniformCrossOver, mutation(&config))
+                            .with_elitism(Elitism::Count(config.elite_count));
+                    if let Some(threshold) = config.speciation_threshold {
+                        ga = ga.with_speciation(Speciation::new(threshold));
+                    }
+                    if let Some(bounds) = bounds(&config) {
+                        ga = ga.with_bounds(bounds);
+                    }
+                    ga
+                })
+                .collect(),
+            config.migration_topology,
+            config.migration_interval.max(1),
+            config.migration_size,
         );
+        let neat = (0..islands)
+            .map(|_| Neat::new(NeatConfig::default(), config.vision_cells, 2))
+            .collect();
+        let nsga2 = (0..islands)
+            .map(|_| {
+                let mut nsga2 = Nsga2::new(UniformCrossOver, mutation(&config));
+                if let Some(bounds) = bounds(&config) {
+                    nsga2 = nsga2.with_bounds(bounds);
+                }
+                nsga2
+            })
+            .collect();
+        let novelty = config
+            .novelty
+            .map(|novelty| NoveltySearch::new(novelty.k, novelty.threshold));
+        let hall_of_fame = HallOfFame::new(config.hall_of_fame_size);
         let age = 0;
-        Self { world, ga, age }
+        Self {
+            worlds,
+            ga,
+            neat,
+            nsga2,
+            novelty,
+            hall_of_fame,
+            lineage: LineageGraph::new(),
+            age,
+            config,
+            rng,
+            scratch: Scratch::default(),
+            island_statistics: Vec::new(),
+            pareto_statistics: Vec::new(),
+        }
     }
+    /// Builds a simulation that replays identically for the same `seed` and
+    /// `config`.
+    pub fn from_seed(seed: u64, config: SimulationConfig) -> Self {
+        Self::randomize(&mut ChaCha8Rng::seed_from_u64(seed), config)
+    }
+    /// The first island's world, the one the app shows.
     pub fn world(&self) -> &World {
-        &self.world
+        &self.worlds[0]
+    }
+    pub fn worlds(&self) -> &[World] {
+        &self.worlds
+    }
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
+    /// Statistics of every island for the last generation, in world order.
+    pub fn island_statistics(&self) -> &[Statistics] {
+        &self.island_statistics
     }
-    pub fn step_forward(&mut self, rng: &mut dyn RngCore) -> Option<Statistics> {
+    /// Pareto fronts of every island for the last generation, when the
+    /// config lists [`Objective`]s; empty otherwise.
+    pub fn pareto_statistics(&self) -> &[ParetoStatistics] {
+        &self.pareto_statistics
+    }
+    /// Returns the first island's statistics whenever a generation ends,
+    /// or in steady-state mode whenever ants are replaced; see
+    /// [`Simulation::island_statistics`] for the others.
+    pub fn step_forward(&mut self) -> Option<Statistics> {
         self.process_motion();
-        self.process_collision(rng);
+        self.process_collision();
         self.process_brain();
 
         self.age += 1;
 
-        if self.age > GENERATION_LENGTH {
-            Some(self.evolve(rng))
-        } else {
-            None
+        match self.config.steady_state {
+            Some(steady_state)
+                if self.config.brain != BrainKind::Neat && self.config.objectives.is_empty() =>
+            {
+                if self.age >= steady_state.interval {
+                    Some(self.replace_worst(steady_state.count))
+                } else {
+                    None
+                }
+            }
+            _ => {
+                if self.age > self.config.generation_length {
+                    Some(self.evolve())
+                } else {
+                    None
+                }
+            }
         }
     }
     pub fn process_motion(&mut self) {
-        for animal in &mut self.world.animals {
+        for animal in self.worlds.iter_mut().flat_map(|world| &mut world.animals) {
             animal.pos += animal.rotation * nalgebra::Vector2::new(animal.speed, 0.0);
+            animal.energy += animal.speed;
+            animal.lifetime += 1;
 
             animal.pos.x = na::wrap(animal.pos.x, 0.0, 1.0);
             animal.pos.y = na::wrap(animal.pos.y, 0.0, 1.0);
+
+            let cell = |coordinate: f32| {
+                ((coordinate * VISITED_GRID as f32) as usize).min(VISITED_GRID - 1)
+            };
+            animal.visited[cell(animal.pos.y) * VISITED_GRID + cell(animal.pos.x)] +=
+                1.0 / self.config.generation_length as f32;
         }
     }
-    pub fn process_collision(&mut self, rng: &mut dyn RngCore) {
-        for animal in &mut self.world.animals {
-            for food in &mut self.world.food {
-                let dist = nalgebra::distance(&animal.pos, &food.pos);
-                if dist <= 0.009 {
-                    food.pos = rng.gen();
-                    animal.score += 1;
+    pub fn process_collision(&mut self) {
+        for world in &mut self.worlds {
+            for animal in &mut world.animals {
+                for food in &mut world.food {
+                    let dist = nalgebra::distance(&animal.pos, &food.pos);
+                    if dist <= self.config.eat_range {
+                        food.pos = self.rng.gen();
+                        animal.score += 1;
+                    }
+                }
+                for obstacle in &mut world.obstacles {
+                    let dist = nalgebra::distance(&animal.pos, &obstacle.pos);
+                    if dist <= self.config.eat_range {
+                        animal.collisions += 1;
+                    }
                 }
-            }
-            for obstacle in &mut self.world.obstacles {
-                let dist = nalgebra::distance(&animal.pos, &obstacle.pos);
-                if dist <= 0.009 {}
             }
         }
     }
     pub fn process_brain(&mut self) {
-        for animal in &mut self.world.animals {
-            let vision = animal.vision.process_vision(
-                animal.pos,
-                animal.rotation,
-                &self.world.food,
-                &self.world.obstacles,
-            );
-            let brain_response = animal.brain.nn.propagate(vision.clone());
-            let speed = brain_response[0].clamp(-SPEED_ACCEL, SPEED_ACCEL);
-            let rotation = brain_response[1].clamp(-ROTATION_ACCEL, ROTATION_ACCEL);
-
-            animal.speed = (animal.speed + speed).clamp(SPEED_MIN, SPEED_MAX);
-            animal.rotation = na::Rotation2::new(animal.rotation.angle() + rotation);
-            animal.vision_input = vision;
-        }
-    }
-    pub fn evolve(&mut self, rng: &mut dyn RngCore) -> Statistics {
+        for world in &mut self.worlds {
+            for animal in &mut world.animals {
+                animal.vision.process_vision_into(
+                    animal.pos,
+                    animal.rotation,
+                    &world.food,
+                    &world.obstacles,
+                    &mut animal.vision_input,
+                );
+                let brain_response = animal.brain.propagate(
+                    &animal.vision_input,
+                    &mut animal.memory,
+                    &mut self.scratch,
+                );
+                let speed =
+                    brain_response[0].clamp(-self.config.speed_accel, self.config.speed_accel);
+                let rotation = brain_response[1]
+                    .clamp(-self.config.rotation_accel, self.config.rotation_accel);
+
+                animal.speed =
+                    (animal.speed + speed).clamp(self.config.speed_min, self.config.speed_max);
+                animal.rotation = na::Rotation2::new(animal.rotation.angle() + rotation);
+            }
+        }
+    }
+    pub fn evolve(&mut self) -> Statistics {
         self.age = 0;
 
-        let current_population: Vec<_> = self
-            .world
-            .animals()
+        self.island_statistics = if self.config.brain == BrainKind::Neat {
+            self.evolve_neat()
+        } else if !self.config.objectives.is_empty() {
+            self.evolve_pareto()
+        } else {
+            self.evolve_fixed()
+        };
+
+        for food in self.worlds.iter_mut().flat_map(|world| &mut world.food) {
+            food.pos = self.rng.gen();
+        }
+        self.island_statistics[0].clone()
+    }
+    /// Steady-state counterpart of [`Simulation::evolve`]: replaces the
+    /// `count` worst ants of every world by offspring, leaving the other
+    /// ants and the food where they are.
+    ///
+    /// Ants are ranked by food eaten per step of their life, scaled to a
+    /// generation, so that older ants don't win by age alone, plus their
+    /// novelty when the config enables it. Each call counts as a
+    /// generation towards the migration interval.
+    pub fn replace_worst(&mut self, count: usize) -> Statistics {
+        self.age = 0;
+        self.island_statistics.clear();
+
+        let mut populations: Vec<Vec<_>> = self
+            .worlds
             .iter()
-            .map(AnimalIndividual::from_animal)
+            .zip(&mut self.ga.islands)
+            .map(|(world, ga)| {
+                let mut population: Vec<_> = world
+                    .animals()
+                    .iter()
+                    .map(|animal| AnimalIndividual {
+                        fitness: animal.score as f32 * self.config.generation_length as f32
+                            / animal.lifetime.max(1) as f32,
+                        chromosome: animal.as_chromosome(),
+                        lineage: animal.lineage.clone(),
+                    })
+                    .collect();
+
+                ga.found(&mut population);
+                if self.config.track_lineage {
+                    self.lineage.record(&population);
+                }
+                self.hall_of_fame.update(&population);
+                self.island_statistics
+                    .push(Statistics::analyze(&population));
+
+                if let (Some(novelty), Some(config)) = (&mut self.novelty, self.config.novelty) {
+                    let scores = novelty.evaluate(world.animals());
+                    for (individual, score) in population.iter_mut().zip(scores) {
+                        individual.fitness += config.weight * score;
+                    }
+                }
+                population
+            })
             .collect();
 
-        // let mut animal_individuals = Vec::new();
-        // for animal in self.world.animals.iter() {
-        //     let animal_individual = AnimalIndividual::from_animal(animal);
+        let replaced = self
+            .ga
+            .replace_worst(&mut self.rng, &mut populations, count);
 
-        //     animal_individuals.push(animal_individual);
-        // }
+        for ((world, population), replaced) in self.worlds.iter_mut().zip(populations).zip(replaced)
+        {
+            for index in replaced {
+                let chromosome = population[index].chromosome.clone();
+                world.animals[index] =
+                    Animal::from_chromosome(chromosome, &mut self.rng, &self.config);
+            }
+            for (animal, individual) in world.animals.iter_mut().zip(population) {
+                animal.lineage = individual.lineage;
+            }
+        }
 
-        let (evolved_population, statistics) = self.ga.evolve(rng, &current_population);
-        self.world.animals = evolved_population
-            .into_iter()
-            .map(|individual| individual.to_animal(rng))
+        self.island_statistics[0].clone()
+    }
+    fn evolve_fixed(&mut self) -> Vec<Statistics> {
+        let current_populations: Vec<Vec<_>> = self
+            .worlds
+            .iter()
+            .zip(&mut self.ga.islands)
+            .map(|(world, ga)| {
+                let mut population: Vec<_> = world
+                    .animals()
+                    .iter()
+                    .map(AnimalIndividual::from_animal)
+                    .collect();
+
+                ga.found(&mut population);
+                if self.config.track_lineage {
+                    self.lineage.record(&population);
+                }
+                self.hall_of_fame.update(&population);
+
+                if let (Some(novelty), Some(config)) = (&mut self.novelty, self.config.novelty) {
+                    let scores = novelty.evaluate(world.animals());
+                    for (individual, score) in population.iter_mut().zip(scores) {
+                        individual.fitness += config.weight * score;
+                    }
+                }
+                population
+            })
             .collect();
 
-        for food in &mut self.world.food {
-            food.pos = rng.gen();
+        let (evolved_populations, statistics) = self.ga.evolve(&mut self.rng, &current_populations);
+
+        for (world, evolved_population) in self.worlds.iter_mut().zip(evolved_populations) {
+            world.animals = evolved_population
+                .into_iter()
+                .map(|individual| individual.to_animal(&mut self.rng, &self.config))
+                .collect();
         }
+
+        statistics
+    }
+    /// Like NEAT islands, NSGA-II islands evolve independently. The
+    /// returned statistics use the food eaten as fitness, and are what the
+    /// mutation adapts to.
+    fn evolve_pareto(&mut self) -> Vec<Statistics> {
+        let mut statistics = Vec::with_capacity(self.worlds.len());
+        self.pareto_statistics.clear();
+
+        for (world, nsga2) in self.worlds.iter_mut().zip(&mut self.nsga2) {
+            let current_population: Vec<_> = world
+                .animals()
+                .iter()
+                .map(|animal| {
+                    MultiObjectiveAnimalIndividual::from_animal(animal, &self.config.objectives)
+                })
+                .collect();
+            let fitness: Vec<_> = world
+                .animals()
+                .iter()
+                .map(AnimalIndividual::from_animal)
+                .collect();
+
+            let (evolved_population, pareto_statistics) =
+                nsga2.evolve(&mut self.rng, &current_population);
+            world.animals = evolved_population
+                .into_iter()
+                .map(|individual| individual.to_animal(&mut self.rng, &self.config))
+                .collect();
+
+            let island_statistics = Statistics::analyze(&fitness);
+            nsga2.mutation_method.adapt(&island_statistics);
+
+            self.hall_of_fame.update(&fitness);
+            statistics.push(island_statistics);
+            self.pareto_statistics.push(pareto_statistics);
+        }
+
+        statistics
+    }
+    /// NEAT islands evolve independently: innovation numbers aren't
+    /// shared between them, so their genomes can't be exchanged.
+    fn evolve_neat(&mut self) -> Vec<Statistics> {
+        let mut statistics = Vec::with_capacity(self.worlds.len());
+
+        for (world, neat) in self.worlds.iter_mut().zip(&mut self.neat) {
+            let current_population: Vec<_> = world
+                .animals()
+                .iter()
+                .map(NeatAnimalIndividual::from_animal)
+                .collect();
+
+            let (evolved_population, island_statistics) =
+                neat.evolve(&mut self.rng, &current_population);
+            world.animals = evolved_population
+                .into_iter()
+                .map(|individual| individual.to_animal(&mut self.rng, &self.config))
+                .collect();
+
+            statistics.push(island_statistics);
+        }
+
         statistics
     }
 }
 
 impl World {
-    pub fn randomize(rng: &mut dyn RngCore) -> Self {
-        let animals = (0..50).map(|_| Animal::randomize(rng)).collect();
-        let food = (0..30).map(|_| Food::randomize(rng)).collect();
+    pub fn randomize(rng: &mut dyn RngCore, config: &SimulationConfig) -> Self {
+        let animals = (0..config.population_size)
+            .map(|_| Animal::randomize(rng, config))
+            .collect();
+        let food = (0..config.food_count)
+            .map(|_| Food::randomize(rng))
+            .collect();
         let mut obstacles = Vec::with_capacity(NUM_OBSTACLES / 4);
 
         for i in 0..99 {
@@ -308,17 +756,25 @@ impl World {
 }
 
 impl Animal {
-    pub fn randomize(rng: &mut dyn RngCore) -> Self {
-        let vision = Vision::default();
-        let brain = Brain::randomize(&vision);
+    pub fn randomize(rng: &mut dyn RngCore, config: &SimulationConfig) -> Self {
+        let vision = Vision::from_config(config);
+        let brain = Brain::randomize(rng, &vision, config.brain, config.weight_init);
+        let memory = brain.initial_state();
         Self {
             pos: rng.gen(),
             rotation: rng.gen(),
             speed: 0.0005,
             vision,
             brain,
+            memory,
+            strategy: initial_strategy(config),
             score: 0,
-            vision_input: vec![0.0; CELLS],
+            energy: 0.0,
+            collisions: 0,
+            lifetime: 0,
+            visited: vec![0.0; VISITED_GRID * VISITED_GRID],
+            lineage: None,
+            vision_input: vec![0.0; config.vision_cells],
         }
     }
     pub fn position(&self) -> &Point2<f32> {
@@ -330,24 +786,85 @@ impl Animal {
     pub fn speed(&self) -> f32 {
         self.speed
     }
+    /// The brain's weights followed by the strategy genes.
     pub fn as_chromosome(&self) -> Chromosome {
-        self.brain.as_chromosome()
+        self.brain
+            .as_chromosome()
+            .into_iter()
+            .chain(self.strategy.iter().copied())
+            .collect()
+    }
+    pub fn from_chromosome(
+        chromosome: Chromosome,
+        rng: &mut dyn RngCore,
+        config: &SimulationConfig,
+    ) -> Self {
+        let mut genes: Vec<f32> = chromosome.into_iter().collect();
+        let strategy_len = initial_strategy(config).len();
+        let strategy = if genes.len() >= strategy_len {
+            genes.split_off(genes.len() - strategy_len)
+        } else {
+            initial_strategy(config)
+        };
+        let chromosome = genes.into_iter().collect();
+
+        let vision = Vision::from_config(config);
+        // A genome that doesn't fit the current vision (e.g. bred under a
+        // different config) gets a fresh brain rather than crashing the run.
+        let brain = Brain::from_chromosome(chromosome, &vision, config.brain)
+            .unwrap_or_else(|_| Brain::randomize(rng, &vision, config.brain, config.weight_init));
+        let memory = brain.initial_state();
+        Self {
+            pos: rng.gen(),
+            rotation: rng.gen(),
+            speed: 0.001,
+            vision,
+            brain,
+            memory,
+            strategy,
+            score: 0,
+            energy: 0.0,
+            collisions: 0,
+            lifetime: 0,
+            visited: vec![0.0; VISITED_GRID * VISITED_GRID],
+            lineage: None,
+            vision_input: Vec::with_capacity(config.vision_cells),
+        }
     }
-    pub fn from_chromosome(chromosome: Chromosome, rng: &mut dyn RngCore) -> Self {
-        let vision = Vision::default();
-        let brain = Brain::from_chromosome(chromosome, &vision);
+    pub fn from_genome(
+        genome: NeatGenome,
+        rng: &mut dyn RngCore,
+        config: &SimulationConfig,
+    ) -> Self {
+        let vision = Vision::from_config(config);
+        let brain = Brain::from_genome(genome)
+            .unwrap_or_else(|_| Brain::randomize(rng, &vision, config.brain, config.weight_init));
+        let memory = brain.initial_state();
         Self {
             pos: rng.gen(),
             rotation: rng.gen(),
             speed: 0.001,
             vision,
             brain,
+            memory,
+            strategy: Vec::new(),
             score: 0,
-            vision_input: Vec::with_capacity(CELLS),
+            energy: 0.0,
+            collisions: 0,
+            lifetime: 0,
+            visited: vec![0.0; VISITED_GRID * VISITED_GRID],
+            lineage: None,
+            vision_input: Vec::with_capacity(config.vision_cells),
         }
     }
 }
 
+impl Behavior for Animal {
+    fn behavior(&self) -> Vec<f32> {
+        self.visited.clone()
+    }
+}
+
 impl Food {
     pub fn randomize(rng: &mut dyn RngCore) -> Self {
         Self { pos: rng.gen() }
@@ -361,3 +878,228 @@ impl Obstacle {
         &self.pos
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_produces_identical_statistics() {
+        let config = SimulationConfig {
+            population_size: 10,
+            generation_length: 100,
+            ..Default::default()
+        };
+        let run = |seed| {
+            let mut simulation = Simulation::from_seed(seed, config.clone());
+            let mut history = Vec::new();
+
+            while history.len() < 3 {
+                if let Some(statistics) = simulation.step_forward() {
+                    history.push(statistics);
+                }
+            }
+            history
+        };
+
+        assert_eq!(run(42), run(42));
+    }
+
+    #[test]
+    fn steady_state_replaces_ants_without_resetting() {
+        let config = SimulationConfig {
+            population_size: 10,
+            steady_state: Some(SteadyState {
+                interval: 20,
+                count: 2,
+            }),
+            ..Default::default()
+        };
+        let mut simulation = Simulation::from_seed(13, config);
+
+        for step in 1..=60 {
+            let statistics = simulation.step_forward();
+            assert_eq!(statistics.is_some(), step % 20 == 0);
+        }
+
+        let lifetimes: Vec<_> = simulation
+            .world()
+            .animals()
+            .iter()
+            .map(|animal| animal.lifetime)
+            .collect();
+        assert_eq!(lifetimes.len(), 10);
+        assert!(lifetimes.contains(&60));
+        assert_eq!(
+            lifetimes.iter().filter(|&&lifetime| lifetime < 20).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn steady_state_scores_novelty_and_migrates() {
+        let config = SimulationConfig {
+            population_size: 10,
+            islands: 2,
+            migration_interval: 1,
+            novelty: Some(NoveltyConfig {
+                k: 3,
+                threshold: 0.0,
+                weight: 1.0,
+            }),
+            steady_state: Some(SteadyState {
+                interval: 10,
+                count: 2,
+            }),
+            ..Default::default()
+        };
+        let mut simulation = Simulation::from_seed(17, config);
+
+        while simulation.step_forward().is_none() {}
+
+        assert_eq!(simulation.novelty.as_ref().unwrap().archive().len(), 20);
+        // Island `i` hands out ids congruent to `i` modulo 2, and migrants
+        // keep theirs.
+        for (island, world) in simulation.worlds().iter().enumerate() {
+            assert!(world
+                .animals()
+                .iter()
+                .any(|animal| animal.lineage.as_ref().unwrap().id % 2 != island as u64));
+        }
+    }
+
+    #[test]
+    fn neat_brains_evolve() {
+        let config = SimulationConfig {
+            population_size: 10,
+            generation_length: 50,
+            brain: BrainKind::Neat,
+            ..Default::default()
+        };
+        let mut simulation = Simulation::from_seed(7, config);
+
+        for _ in 0..3 {
+            simulation.evolve();
+        }
+
+        assert_eq!(simulation.world().animals().len(), 10);
+        assert!(simulation
+            .world()
+            .animals()
+            .iter()
+            .all(|animal| animal.brain.genome().is_some()));
+        assert!(!simulation.neat[0].species().is_empty());
+    }
+
+    #[test]
+    fn lineage_traces_ants_back_to_founders() {
+        let config = SimulationConfig {
+            population_size: 10,
+            generation_length: 20,
+            elite_count: 1,
+            islands: 2,
+            track_lineage: true,
+            ..Default::default()
+        };
+        let mut simulation = Simulation::from_seed(5, config);
+
+        for _ in 0..3 {
+            while simulation.step_forward().is_none() {}
+        }
+
+        let mut ids: Vec<u64> = simulation
+            .worlds()
+            .iter()
+            .flat_map(|world| world.animals())
+            .map(|animal| animal.lineage.as_ref().unwrap().id)
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 20);
+
+        let graph = &simulation.lineage;
+        assert_eq!(
+            graph
+                .nodes()
+                .filter(|node| node.birth_generation == 0)
+                .count(),
+            20
+        );
+        for node in graph.nodes() {
+            assert_eq!(node.parents.is_empty(), node.birth_generation == 0);
+            for parent in &node.parents {
+                assert!(graph.get(*parent).unwrap().birth_generation < node.birth_generation);
+            }
+        }
+    }
+
+    #[test]
+    fn novelty_fills_the_archive_and_hall_of_fame() {
+        let config = SimulationConfig {
+            population_size: 10,
+            generation_length: 50,
+            novelty: Some(NoveltyConfig {
+                k: 3,
+                threshold: 0.0,
+                weight: 1.0,
+            }),
+            hall_of_fame_size: 4,
+            ..Default::default()
+        };
+        let mut simulation = Simulation::from_seed(11, config);
+
+        for _ in 0..2 {
+            while simulation.step_forward().is_none() {}
+        }
+
+        assert_eq!(simulation.novelty.as_ref().unwrap().archive().len(), 20);
+        assert_eq!(simulation.hall_of_fame.members().len(), 4);
+        assert!(simulation.world().animals()[0]
+            .behavior()
+            .iter()
+            .all(|&fraction| fraction == 0.0));
+    }
+
+    #[test]
+    fn objectives_report_a_pareto_front() {
+        let config = SimulationConfig {
+            population_size: 10,
+            generation_length: 50,
+            objectives: vec![Objective::Food, Objective::Energy],
+            ..Default::default()
+        };
+        let mut simulation = Simulation::from_seed(5, config);
+
+        while simulation.step_forward().is_none() {}
+
+        let pareto = &simulation.pareto_statistics()[0];
+        assert_eq!(pareto.front_sizes.iter().sum::<usize>(), 10);
+        assert!(pareto
+            .pareto_front
+            .iter()
+            .all(|objectives| objectives.len() == 2 && objectives[1] < 0.0));
+        assert_eq!(simulation.world().animals().len(), 10);
+    }
+
+    #[test]
+    fn every_island_keeps_its_population() {
+        let config = SimulationConfig {
+            population_size: 8,
+            generation_length: 20,
+            islands: 3,
+            migration_interval: 1,
+            ..Default::default()
+        };
+        let mut simulation = Simulation::from_seed(3, config);
+
+        for _ in 0..2 {
+            simulation.evolve();
+        }
+
+        assert_eq!(simulation.island_statistics().len(), 3);
+        assert!(simulation
+            .worlds()
+            .iter()
+            .all(|world| world.animals().len() == 8));
+    }
+}